@@ -0,0 +1,116 @@
+// Compares the bullet hit test of every bullet against every actor with the uniform grid broadphase.
+// cargo bench --bench collision_broadphase
+
+use std::time::{Duration, Instant};
+
+use nalgebra::Vector3;
+
+use third_impact::game_module::collision::intersect_segment_sphere;
+use third_impact::game_module::game_constants::SPATIAL_GRID_CELL_SIZE;
+use third_impact::game_module::spatial_grid::SpatialGrid;
+
+const BULLET_COUNT: usize = 500;
+const ACTOR_COUNT: usize = 200;
+const FRAME_COUNT: usize = 1000;
+const WORLD_SIZE: f32 = 1000.0;
+const WORLD_HEIGHT: f32 = 100.0;
+const BULLET_SPEED: f32 = 100.0;
+const DELTA_TIME: f32 = 1.0 / 60.0;
+
+struct BenchActor {
+    _center: Vector3<f32>,
+    _radius: f32,
+}
+
+struct BenchBullet {
+    _start: Vector3<f32>,
+    _end: Vector3<f32>,
+}
+
+fn random_position() -> Vector3<f32> {
+    Vector3::new(
+        rand::random::<f32>() * WORLD_SIZE,
+        rand::random::<f32>() * WORLD_HEIGHT,
+        rand::random::<f32>() * WORLD_SIZE,
+    )
+}
+
+fn random_direction() -> Vector3<f32> {
+    Vector3::new(
+        rand::random::<f32>() - 0.5,
+        (rand::random::<f32>() - 0.5) * 0.1,
+        rand::random::<f32>() - 0.5,
+    ).normalize()
+}
+
+fn create_actors() -> Vec<BenchActor> {
+    (0..ACTOR_COUNT).map(|_| BenchActor {
+        _center: random_position(),
+        _radius: 3.0 + rand::random::<f32>() * 7.0,
+    }).collect()
+}
+
+fn create_bullets() -> Vec<BenchBullet> {
+    (0..BULLET_COUNT).map(|_| {
+        let start = random_position();
+        let end = &start + random_direction() * BULLET_SPEED * DELTA_TIME;
+        BenchBullet { _start: start, _end: end }
+    }).collect()
+}
+
+fn bench_brute_force(actors: &Vec<BenchActor>, bullets: &Vec<BenchBullet>) -> (Duration, usize) {
+    let mut hit_count: usize = 0;
+    let start_time = Instant::now();
+    for _ in 0..FRAME_COUNT {
+        for bullet in bullets.iter() {
+            for actor in actors.iter() {
+                if intersect_segment_sphere(&bullet._start, &bullet._end, &actor._center, actor._radius).is_some() {
+                    hit_count += 1;
+                    break;
+                }
+            }
+        }
+    }
+    (start_time.elapsed(), hit_count)
+}
+
+fn bench_spatial_grid(actors: &Vec<BenchActor>, bullets: &Vec<BenchBullet>) -> (Duration, usize) {
+    let mut spatial_grid: SpatialGrid<usize> = SpatialGrid::create_spatial_grid(SPATIAL_GRID_CELL_SIZE);
+    let mut candidates: Vec<usize> = Vec::new();
+    let mut hit_count: usize = 0;
+    let start_time = Instant::now();
+    for _ in 0..FRAME_COUNT {
+        // rebuilt every frame as the actor manager does
+        spatial_grid.clear_spatial_grid();
+        for (actor_index, actor) in actors.iter().enumerate() {
+            spatial_grid.insert_item(actor_index, &actor._center, actor._radius);
+        }
+
+        for bullet in bullets.iter() {
+            spatial_grid.query_segment(&bullet._start, &bullet._end, &mut candidates);
+            for actor_index in candidates.iter() {
+                let actor = &actors[*actor_index];
+                if intersect_segment_sphere(&bullet._start, &bullet._end, &actor._center, actor._radius).is_some() {
+                    hit_count += 1;
+                    break;
+                }
+            }
+        }
+    }
+    (start_time.elapsed(), hit_count)
+}
+
+fn main() {
+    let actors = create_actors();
+    let bullets = create_bullets();
+
+    let (brute_force_time, brute_force_hit_count) = bench_brute_force(&actors, &bullets);
+    let (spatial_grid_time, spatial_grid_hit_count) = bench_spatial_grid(&actors, &bullets);
+    assert_eq!(brute_force_hit_count, spatial_grid_hit_count);
+
+    let frame_count = FRAME_COUNT as f64;
+    println!("bullets: {}, actors: {}, frames: {}", BULLET_COUNT, ACTOR_COUNT, FRAME_COUNT);
+    println!("brute force:  {:.3} ms/frame", brute_force_time.as_secs_f64() * 1000.0 / frame_count);
+    println!("spatial grid: {:.3} ms/frame (including rebuild)", spatial_grid_time.as_secs_f64() * 1000.0 / frame_count);
+    println!("speed up: {:.2}x", brute_force_time.as_secs_f64() / spatial_grid_time.as_secs_f64());
+}
@@ -38,6 +38,8 @@ pub struct SceneDataCreateInfo {
     pub _directional_lights: HashMap<String, DirectionalLightCreateInfo>,
     pub _effects: HashMap<String, EffectCreateInfo>,
     pub _static_objects: HashMap<String, RenderObjectCreateInfo>,
+    // names of the static objects which block the bullets
+    pub _static_collision_objects: Vec<String>,
     pub _skeletal_objects: HashMap<String, RenderObjectCreateInfo>,
     pub _level_data: LevelData,
 }
@@ -50,6 +52,7 @@ impl Default for SceneDataCreateInfo {
             _directional_lights: HashMap::new(),
             _effects: HashMap::new(),
             _static_objects: HashMap::new(),
+            _static_collision_objects: Vec::new(),
             _skeletal_objects: HashMap::new(),
             _level_data: LevelData::default(),
         }
@@ -73,6 +76,9 @@ pub struct ProjectSceneManager {
     pub _effect_id_map: EffectIDMap,
     pub _static_render_object_map: RenderObjectMap,
     pub _skeletal_render_object_map: RenderObjectMap,
+    pub _static_collision_objects: Vec<RcRefCell<RenderObjectData>>,
+    pub _render_object_pool: Vec<RcRefCell<RenderObjectData>>,
+    pub _render_object_pool_visibilities: Vec<bool>,
     pub _static_render_elements: Vec<RenderElementData>,
     pub _static_shadow_render_elements: Vec<RenderElementData>,
     pub _skeletal_render_elements: Vec<RenderElementData>,
@@ -146,6 +152,9 @@ impl ProjectSceneManager {
             _effect_id_map: HashMap::default(),
             _static_render_object_map: HashMap::new(),
             _skeletal_render_object_map: HashMap::new(),
+            _static_collision_objects: Vec::new(),
+            _render_object_pool: Vec::new(),
+            _render_object_pool_visibilities: Vec::new(),
             _static_render_elements: Vec::new(),
             _static_shadow_render_elements: Vec::new(),
             _skeletal_render_elements: Vec::new(),
@@ -179,6 +188,7 @@ impl ProjectSceneManager {
     pub fn get_effect_manager_mut(&self) -> &mut EffectManager { unsafe { &mut *(self._effect_manager as *mut EffectManager) } }
     pub fn set_effect_manager(&mut self, effect_manager: *const EffectManager) { self._effect_manager = effect_manager; }
     pub fn get_sea_height(&self) -> f32 { self._sea_height }
+    pub fn get_static_collision_objects(&self) -> &Vec<RcRefCell<RenderObjectData>> { &self._static_collision_objects }
     pub fn add_camera_object(&mut self, object_name: &str, camera_create_info: &CameraCreateInfo) -> Rc<CameraObjectData> {
         let new_object_name = system::generate_unique_name(&self._camera_object_map, object_name);
         let camera_object_data = Rc::new(CameraObjectData::create_camera_object_data(&new_object_name, camera_create_info));
@@ -200,6 +210,25 @@ impl ProjectSceneManager {
         render_object_data
     }
 
+    // pooled render objects are created once and rendered with the static objects only while visible
+    pub fn add_pooled_render_object(&mut self, object_name: &str, render_object_create_info: &RenderObjectCreateInfo) -> usize {
+        let model_data = self.get_engine_resources().get_model_data(&render_object_create_info._model_data_name);
+        let render_object_handle = self._render_object_pool.len();
+        let new_object_name = format!("{}_{}", object_name, render_object_handle);
+        let render_object_data = newRcRefCell(RenderObjectData::create_render_object_data(&new_object_name, &model_data, &render_object_create_info));
+        self._render_object_pool.push(render_object_data);
+        self._render_object_pool_visibilities.push(false);
+        render_object_handle
+    }
+
+    pub fn get_pooled_render_object(&self, render_object_handle: usize) -> &RcRefCell<RenderObjectData> {
+        &self._render_object_pool[render_object_handle]
+    }
+
+    pub fn set_pooled_render_object_visible(&mut self, render_object_handle: usize, visible: bool) {
+        self._render_object_pool_visibilities[render_object_handle] = visible;
+    }
+
     pub fn add_skeletal_render_object(&mut self, object_name: &str, render_object_create_info: &RenderObjectCreateInfo) -> RcRefCell<RenderObjectData> {
         let model_data = self.get_engine_resources().get_model_data(&render_object_create_info._model_data_name);
         let new_object_name = system::generate_unique_name(&self._skeletal_render_object_map, &object_name);
@@ -276,20 +305,20 @@ impl ProjectSceneManager {
         false
     }
 
-    pub fn gather_render_elements(
+    pub fn gather_render_elements<'a, T>(
         render_object_type: RenderObjectType,
         camera: &CameraObjectData,
         light: &DirectionalLightData,
-        render_object_map: &RenderObjectMap,
+        render_objects: T,
         render_elements: &mut Vec<RenderElementData>,
         render_shadow_elements: &mut Vec<RenderElementData>,
         render_element_transform_offset: &mut usize,
         render_element_transform_metrices: &mut Vec<Matrix4<f32>>
-    ) {
+    ) where T: Iterator<Item = &'a RcRefCell<RenderObjectData>> {
         render_elements.clear();
         render_shadow_elements.clear();
 
-        for (_key, render_object_data_ref) in render_object_map.iter() {
+        for render_object_data_ref in render_objects {
             let render_object_data = render_object_data_ref.borrow();
             let model_data = ptr_as_ref(render_object_data.get_model_data().as_ptr());
             let mesh_data = model_data.get_mesh_data().borrow();
@@ -380,6 +409,7 @@ impl ProjectSceneManager {
             _directional_lights: HashMap::new(),
             _effects: HashMap::new(),
             _static_objects: HashMap::new(),
+            _static_collision_objects: Vec::new(),
             _skeletal_objects: HashMap::new(),
             _level_data: LevelData::default(),
         };
@@ -530,9 +560,12 @@ impl ProjectSceneManager {
             self.add_effect(object_name, effect_create_info);
         }
 
-        // static objects
+        // static objects, only the objects listed in the scene data collide such as the stage and not the gizmos
         for (object_name, render_object_create_info) in scene_data_create_info._static_objects.iter() {
-            self.add_static_render_object(object_name, render_object_create_info);
+            let render_object = self.add_static_render_object(object_name, render_object_create_info);
+            if scene_data_create_info._static_collision_objects.contains(object_name) {
+                self._static_collision_objects.push(render_object);
+            }
         }
 
         // skeletal objects
@@ -550,6 +583,9 @@ impl ProjectSceneManager {
         self._effect_id_map.clear();
         self._static_render_object_map.clear();
         self._skeletal_render_object_map.clear();
+        self._static_collision_objects.clear();
+        self._render_object_pool.clear();
+        self._render_object_pool_visibilities.clear();
         self._static_render_elements.clear();
         self._static_shadow_render_elements.clear();
         self._skeletal_render_elements.clear();
@@ -611,6 +647,9 @@ impl ProjectSceneManager {
             };
             scene_data_create_info._static_objects.insert(object._render_object_name.clone(), static_object_create_info);
         }
+        for static_object in self._static_collision_objects.iter() {
+            scene_data_create_info._static_collision_objects.push(static_object.borrow()._render_object_name.clone());
+        }
         // skeletal objects
         for skeletal_object in self._skeletal_render_object_map.values() {
             let object = skeletal_object.borrow();
@@ -648,6 +687,12 @@ impl ProjectSceneManager {
             render_object_data.borrow_mut().update_render_object_data(delta_time as f32);
         }
 
+        for (render_object_data, visible) in self._render_object_pool.iter().zip(self._render_object_pool_visibilities.iter()) {
+            if *visible {
+                render_object_data.borrow_mut().update_render_object_data(delta_time as f32);
+            }
+        }
+
         for (_key, render_object_data) in self._skeletal_render_object_map.iter() {
             render_object_data.borrow_mut().update_render_object_data(delta_time as f32);
         }
@@ -656,11 +701,16 @@ impl ProjectSceneManager {
         {
             self._render_element_transform_count = 0;
 
+            let visible_pooled_render_objects = self._render_object_pool.iter()
+                .zip(self._render_object_pool_visibilities.iter())
+                .filter(|(_render_object_data, visible)| **visible)
+                .map(|(render_object_data, _visible)| render_object_data);
+
             ProjectSceneManager::gather_render_elements(
                 RenderObjectType::Static,
                 &main_camera,
                 &main_light,
-                &self._static_render_object_map,
+                self._static_render_object_map.values().chain(visible_pooled_render_objects),
                 &mut self._static_render_elements,
                 &mut self._static_shadow_render_elements,
                 &mut self._render_element_transform_count,
@@ -671,7 +721,7 @@ impl ProjectSceneManager {
                 RenderObjectType::Skeletal,
                 &main_camera,
                 &main_light,
-                &self._skeletal_render_object_map,
+                self._skeletal_render_object_map.values(),
                 &mut self._skeletal_render_elements,
                 &mut self._skeletal_shadow_render_elements,
                 &mut self._render_element_transform_count,
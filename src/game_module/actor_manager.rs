@@ -1,21 +1,71 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 
+use nalgebra::Vector3;
+
 use rust_engine_3d::renderer::render_object::{RenderObjectData, RenderObjectCreateInfo};
-use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_mut, ptr_as_ref};
 use crate::application::project_scene_manager::ProjectSceneManager;
 use crate::game_module::actors::actor::ActorController;
+use crate::game_module::faction::{FactionId, FactionRelation, FactionTableData};
 use crate::game_module::game_client::GameClient;
+use crate::game_module::game_event_manager::GameEvent;
+use crate::game_module::game_constants::{PLAYER_RESPAWN_TIME, SPATIAL_GRID_CELL_SIZE};
 use crate::game_module::level_datas::spawn_point::{SpawnPointType, ShipSpawnPointData};
+use crate::game_module::spatial_grid::SpatialGrid;
+use crate::resource::project_resource::DEFAULT_GAME_DATA_NAME;
 
 
-pub type ActorMap = HashMap<u64, Rc<ActorController>>;
+// The id is the index of the actor slot, and the generation of the slot increases when the actor is removed,
+// so the handles of the removed actors never resolve to the actor which reuses the slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ActorHandle {
+    pub _id: usize,
+    pub _generation: u32,
+}
+
+impl Default for ActorHandle {
+    fn default() -> ActorHandle {
+        ActorHandle {
+            _id: usize::MAX,
+            _generation: 0,
+        }
+    }
+}
+
+pub struct ActorSlot {
+    pub _generation: u32,
+    pub _actor: Option<Rc<ActorController>>,
+}
+
+// combat record of an actor, read from the game events
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CombatStats {
+    pub _shot_count: u32,
+    pub _hit_count: u32,
+    pub _kill_count: u32,
+    pub _damage_dealt: f32,
+}
 
 pub struct ActorManager {
     pub _game_client: *const GameClient,
-    pub _id_generator: u64,
-    pub _player_actor: *const ActorController,
-    pub _actors: ActorMap,
+    pub _player_actor_handle: Option<ActorHandle>,
+    pub _player_spawn_point_data: Option<ShipSpawnPointData>,
+    pub _player_position: Vector3<f32>,
+    // lives left including the current ship
+    pub _player_lives: u32,
+    pub _is_player_dead: bool,
+    pub _player_dead_time: f32,
+    pub _actor_slots: Vec<ActorSlot>,
+    pub _free_actor_ids: Vec<usize>,
+    // the actors removed while the actors are updated, such as a kill by a hitscan weapon, are removed after the update loop
+    pub _is_updating_actors: bool,
+    pub _pending_remove_actor_handles: Vec<ActorHandle>,
+    pub _actor_names: HashMap<String, ActorHandle>,
+    // recorded by the handle, so the credit remains after the actor is removed
+    pub _combat_stats: HashMap<ActorHandle, CombatStats>,
+    pub _spatial_grid: SpatialGrid<ActorHandle>,
+    pub _faction_table_data: Option<RcRefCell<FactionTableData>>,
 }
 
 pub fn calc_floating_height(render_object: &RenderObjectData) -> f32 {
@@ -26,9 +76,20 @@ impl ActorManager {
     pub fn create_actor_manager() -> Box<ActorManager> {
         Box::new(ActorManager {
             _game_client: std::ptr::null(),
-            _id_generator: 0,
-            _player_actor: std::ptr::null(),
-            _actors: HashMap::new(),
+            _player_actor_handle: None,
+            _player_spawn_point_data: None,
+            _player_position: Vector3::zeros(),
+            _player_lives: 0,
+            _is_player_dead: false,
+            _player_dead_time: 0.0,
+            _actor_slots: Vec::new(),
+            _free_actor_ids: Vec::new(),
+            _is_updating_actors: false,
+            _pending_remove_actor_handles: Vec::new(),
+            _actor_names: HashMap::new(),
+            _combat_stats: HashMap::new(),
+            _spatial_grid: SpatialGrid::create_spatial_grid(SPATIAL_GRID_CELL_SIZE),
+            _faction_table_data: None,
         })
     }
 
@@ -40,14 +101,27 @@ impl ActorManager {
     }
     pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
     pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
-    pub fn generate_id(&mut self) -> u64 {
-        let id = self._id_generator;
-        self._id_generator += 1;
-        id
+    pub fn allocate_actor_handle(&mut self) -> ActorHandle {
+        match self._free_actor_ids.pop() {
+            Some(actor_id) => ActorHandle {
+                _id: actor_id,
+                _generation: self._actor_slots[actor_id]._generation,
+            },
+            None => {
+                self._actor_slots.push(ActorSlot {
+                    _generation: 0,
+                    _actor: None,
+                });
+                ActorHandle {
+                    _id: self._actor_slots.len() - 1,
+                    _generation: 0,
+                }
+            }
+        }
     }
 
-    pub fn create_actor(&mut self, game_client: &GameClient, spawn_point_data: &ShipSpawnPointData, is_player_actor: bool) {
-        let id = self.generate_id();
+    pub fn create_actor(&mut self, game_client: &GameClient, spawn_point_data: &ShipSpawnPointData, is_player_actor: bool) -> ActorHandle {
+        let actor_handle = self.allocate_actor_handle();
         let project_scene_manager = game_client.get_project_scene_manager_mut();
 
         // create ship render object
@@ -65,31 +139,174 @@ impl ActorManager {
             &render_object_create_info
         );
 
+        // the spawn point can override the ai profile of the ship
+        let ai_profile_data = if spawn_point_data._ai_profile_name.is_empty() {
+            ship_data.borrow()._ai_profile_data.clone()
+        } else {
+            game_client.get_project_resources().get_ai_profile_data(&spawn_point_data._ai_profile_name).clone()
+        };
+
         // create actor
-        let actor = ActorController::create_actor_controller(id, &ship_data, &actor_render_object, is_player_actor);
+        let faction_id = self.get_faction_table_data().get_faction_id(&spawn_point_data._faction_name, is_player_actor);
+        let actor = ActorController::create_actor_controller(actor_handle, &ship_data, &actor_render_object, &ai_profile_data, faction_id, is_player_actor);
         if is_player_actor {
-            self._player_actor = actor.as_ref();
+            self._player_actor_handle = Some(actor_handle);
+            self._player_position.clone_from(&spawn_point_data._position);
         };
         ptr_as_mut(actor.as_ref()).initialize_actor(project_scene_manager);
 
         // regist actor
-        self._actors.insert(id, actor);
+        if false == spawn_point_data._actor_name.is_empty() {
+            self._actor_names.insert(spawn_point_data._actor_name.clone(), actor_handle);
+        }
+        self._actor_slots[actor_handle._id]._actor = Some(actor);
+        game_client.get_game_event_manager_mut().publish_event(GameEvent::ActorSpawned {
+            _actor_handle: actor_handle,
+            _is_player_actor: is_player_actor,
+        });
+        actor_handle
     }
+    // a stale handle is ignored, so the render objects are never removed twice
     pub fn remove_actor(&mut self, project_scene_manager: &mut ProjectSceneManager, actor: &mut ActorController) {
-        actor.remove_actor(project_scene_manager);
-        self._actors.remove(&actor.get_actor_id());
+        let actor_handle = actor.get_actor_handle();
+        if self._is_updating_actors {
+            if false == self._pending_remove_actor_handles.contains(&actor_handle) {
+                self._pending_remove_actor_handles.push(actor_handle);
+            }
+            return;
+        }
+        if self.get_actor(actor_handle).is_some() {
+            actor.remove_actor(project_scene_manager);
+            let actor_slot = &mut self._actor_slots[actor_handle._id];
+            actor_slot._generation = actor_slot._generation.wrapping_add(1);
+            actor_slot._actor = None;
+            self._free_actor_ids.push(actor_handle._id);
+        }
+    }
+    pub fn remove_all_actors(&mut self, project_scene_manager: &mut ProjectSceneManager) {
+        // the slots are kept, so the generations stay valid for the handles still held by anyone
+        for actor_id in 0..self._actor_slots.len() {
+            if let Some(actor) = self._actor_slots[actor_id]._actor.as_ref() {
+                self.remove_actor(project_scene_manager, ptr_as_mut(actor.as_ref()));
+            }
+        }
+        self._actor_names.clear();
+        self._combat_stats.clear();
+        self._player_actor_handle = None;
+        self._player_spawn_point_data = None;
+        self._is_player_dead = false;
+        self._spatial_grid.clear_spatial_grid();
+    }
+    // none before the spawn or when the player actor was removed
+    pub fn get_player_actor(&self) -> Option<&ActorController> {
+        self.get_actor(self._player_actor_handle?)
+    }
+    pub fn get_player_actor_mut(&self) -> Option<&mut ActorController> {
+        self.get_player_actor().map(|player_actor| ptr_as_mut(player_actor))
+    }
+    // position of the player ship, or where it was destroyed while waiting for the respawn
+    pub fn get_player_position(&self) -> Option<&Vector3<f32>> {
+        if self._player_actor_handle.is_some() { Some(&self._player_position) } else { None }
+    }
+    pub fn get_player_lives(&self) -> u32 {
+        self._player_lives
+    }
+    pub fn is_player_dead(&self) -> bool {
+        self._is_player_dead
+    }
+    pub fn get_player_dead_time(&self) -> f32 {
+        self._player_dead_time
+    }
+    pub fn get_player_respawn_time(&self) -> f32 {
+        0f32.max(PLAYER_RESPAWN_TIME - self._player_dead_time)
+    }
+    pub fn is_player_out_of_lives(&self) -> bool {
+        self._is_player_dead && 0 == self._player_lives
+    }
+    pub fn get_actor(&self, actor_handle: ActorHandle) -> Option<&ActorController> {
+        let actor_slot = self._actor_slots.get(actor_handle._id)?;
+        if actor_handle._generation != actor_slot._generation {
+            return None;
+        }
+        actor_slot._actor.as_ref().map(|actor| actor.as_ref())
+    }
+    pub fn get_actors(&self) -> impl Iterator<Item=&ActorController> {
+        self._actor_slots.iter().filter_map(|actor_slot| actor_slot._actor.as_ref().map(|actor| actor.as_ref()))
+    }
+    pub fn get_combat_stats_mut(&mut self, actor_handle: ActorHandle) -> &mut CombatStats {
+        self._combat_stats.entry(actor_handle).or_insert_with(CombatStats::default)
+    }
+    pub fn record_kill(&mut self, killer_actor_handle: ActorHandle) {
+        self.get_combat_stats_mut(killer_actor_handle)._kill_count += 1;
     }
-    pub fn get_player_actor(&self) -> &ActorController {
-        ptr_as_ref(self._player_actor)
+    pub fn get_kill_count(&self, actor_handle: ActorHandle) -> u32 {
+        self._combat_stats.get(&actor_handle).map_or(0, |combat_stats| combat_stats._kill_count)
     }
-    pub fn get_player_actor_mut(&self) -> &mut ActorController { ptr_as_mut(self._player_actor) }
+    pub fn get_combat_stats(&self, actor_handle: ActorHandle) -> Option<&CombatStats> {
+        self._combat_stats.get(&actor_handle)
+    }
+    // the name stays registered after the actor is removed, until the mission ends
+    pub fn is_actor_name_registered(&self, actor_name: &str) -> bool {
+        self._actor_names.contains_key(actor_name)
+    }
+    pub fn get_actor_by_name(&self, actor_name: &str) -> Option<&ActorController> {
+        self.get_actor(*self._actor_names.get(actor_name)?)
+    }
+    pub fn get_faction_table_data(&self) -> &FactionTableData {
+        ptr_as_ref(self._faction_table_data.as_ref().unwrap().as_ptr())
+    }
+    // bullets hit every actor except the allies, the ai only targets the hostile actors
+    pub fn is_allied_faction(&self, faction_id: FactionId, actor: &ActorController) -> bool {
+        FactionRelation::Allied == self.get_faction_table_data().get_relation(faction_id, actor.get_faction_id())
+    }
+    pub fn is_hostile_actor(&self, actor_a: &ActorController, actor_b: &ActorController) -> bool {
+        self.get_faction_table_data().is_hostile(actor_a.get_faction_id(), actor_b.get_faction_id())
+    }
+    pub fn get_hostile_actor_count(&self, faction_id: FactionId) -> usize {
+        let faction_table_data = self.get_faction_table_data();
+        self.get_actors().filter(|actor| faction_table_data.is_hostile(faction_id, actor.get_faction_id()) && false == actor.get_ship().is_destroyed()).count()
+    }
+    pub fn get_spatial_grid_mut(&mut self) -> &mut SpatialGrid<ActorHandle> { &mut self._spatial_grid }
     pub fn spawn_actors(&mut self) {
         let game_client = ptr_as_ref(self._game_client);
         let level_data = game_client.get_project_scene_manager().get_level_data();
+        let faction_table_name = if level_data._faction_table_name.is_empty() { DEFAULT_GAME_DATA_NAME } else { &level_data._faction_table_name };
+        self._faction_table_data = Some(game_client.get_project_resources().get_faction_table_data(faction_table_name).clone());
+
+        self._player_lives = level_data._player_lives.max(1);
+        self._is_player_dead = false;
+        self._player_dead_time = 0.0;
         for spawn_point_type in level_data._spawn_point_datas.iter() {
             match spawn_point_type {
-                SpawnPointType::Player(spawn_point_data) => self.create_actor(game_client, spawn_point_data, true),
-                SpawnPointType::NonPlayer(spawn_point_data) => self.create_actor(game_client, spawn_point_data, false),
+                SpawnPointType::Player(spawn_point_data) => {
+                    self._player_spawn_point_data = Some(spawn_point_data.clone());
+                    self.create_actor(game_client, spawn_point_data, true);
+                },
+                SpawnPointType::NonPlayer(spawn_point_data) => { self.create_actor(game_client, spawn_point_data, false); },
+                // the spawners are run by the spawn manager
+                _ => (),
+            }
+        }
+    }
+
+    pub fn process_game_events(&mut self, game_client: &GameClient) {
+        for game_event in game_client.get_game_event_manager().get_events().iter() {
+            match game_event {
+                GameEvent::ActorSpawned { _actor_handle, .. } => {
+                    // every spawned actor is listed even if it never fights
+                    self.get_combat_stats_mut(*_actor_handle);
+                },
+                GameEvent::WeaponFired { _owner_actor_handle, .. } => {
+                    self.get_combat_stats_mut(*_owner_actor_handle)._shot_count += 1;
+                },
+                GameEvent::ActorDamaged { _attacker_actor_handle, _damage_result, .. } => {
+                    let combat_stats = self.get_combat_stats_mut(*_attacker_actor_handle);
+                    combat_stats._hit_count += 1;
+                    combat_stats._damage_dealt += _damage_result._shield_damage + _damage_result._hull_damage;
+                },
+                GameEvent::ActorDestroyed { _attacker_actor_handle, .. } => {
+                    self.record_kill(*_attacker_actor_handle);
+                },
                 _ => (),
             }
         }
@@ -97,10 +314,82 @@ impl ActorManager {
 
     pub fn update_actor_manager(&mut self, delta_time: f32) {
         let game_client = ptr_as_ref(self._game_client);
-        for actor_ref in self._actors.values() {
-            let actor = ptr_as_mut(actor_ref.as_ref());
-            if actor._is_player_actor {
-                actor.update_actor_controller(game_client, delta_time);
+        self.process_game_events(game_client);
+        // the actors may be spawned while updating, the removal waits until the loop ends
+        self._is_updating_actors = true;
+        for actor_id in 0..self._actor_slots.len() {
+            if let Some(actor) = self._actor_slots[actor_id]._actor.as_ref() {
+                ptr_as_mut(actor.as_ref()).update_actor_controller(game_client, delta_time);
+            }
+        }
+        self._is_updating_actors = false;
+        self.remove_pending_actors(game_client.get_project_scene_manager_mut());
+        self.update_player_lifecycle(game_client, delta_time);
+        self.remove_destroyed_actors(game_client.get_project_scene_manager_mut());
+        self.update_spatial_grid();
+    }
+
+    pub fn remove_pending_actors(&mut self, project_scene_manager: &mut ProjectSceneManager) {
+        let pending_remove_actor_handles = std::mem::take(&mut self._pending_remove_actor_handles);
+        for actor_handle in pending_remove_actor_handles.iter() {
+            if let Some(actor) = self.get_actor(*actor_handle) {
+                self.remove_actor(project_scene_manager, ptr_as_mut(actor));
+            }
+        }
+    }
+
+    // deferred removal of the actors which finished the destruction sequence
+    pub fn remove_destroyed_actors(&mut self, project_scene_manager: &mut ProjectSceneManager) {
+        for actor_id in 0..self._actor_slots.len() {
+            if let Some(actor) = self._actor_slots[actor_id]._actor.as_ref() {
+                if actor.is_destruction_finished() {
+                    self.remove_actor(project_scene_manager, ptr_as_mut(actor.as_ref()));
+                }
+            }
+        }
+    }
+
+    pub fn update_player_lifecycle(&mut self, game_client: &GameClient, delta_time: f32) {
+        if self._player_actor_handle.is_none() {
+            return;
+        }
+
+        // the player actor is dead when it is destroyed or already removed
+        let player_actor = self.get_player_actor().map(|player_actor| ptr_as_mut(player_actor));
+        if false == self._is_player_dead {
+            match player_actor.as_ref() {
+                Some(player_actor) if false == player_actor.get_ship().is_destroyed() => {
+                    self._player_position.clone_from(player_actor.get_transform().get_position());
+                },
+                _ => {
+                    self._is_player_dead = true;
+                    self._player_dead_time = 0.0;
+                    self._player_lives -= 1;
+                }
+            }
+            return;
+        }
+
+        self._player_dead_time += delta_time;
+        if 0 < self._player_lives && PLAYER_RESPAWN_TIME <= self._player_dead_time {
+            if let Some(spawn_point_data) = self._player_spawn_point_data.clone() {
+                if let Some(player_actor) = player_actor {
+                    self.remove_actor(game_client.get_project_scene_manager_mut(), player_actor);
+                }
+                self.create_actor(game_client, &spawn_point_data, true);
+                self._is_player_dead = false;
+            }
+        }
+    }
+
+    // removed actors stay in the grid until the next rebuild, so the queried handles are resolved with get_actor
+    pub fn update_spatial_grid(&mut self) {
+        self._spatial_grid.clear_spatial_grid();
+        for actor_slot in self._actor_slots.iter() {
+            // the wrecks are neither hit nor targeted
+            if let Some(actor) = actor_slot._actor.as_ref().filter(|actor| false == actor.is_dying()) {
+                let bound_box = actor.get_bound_box();
+                self._spatial_grid.insert_item(actor.get_actor_handle(), &bound_box._center, bound_box._radius);
             }
         }
     }
@@ -6,6 +6,9 @@ use rust_engine_3d::renderer::transform_object::TransformObjectData;
 use rust_engine_3d::utilities::bounding_box::BoundingBox;
 use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_mut};
 use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::actor_manager::ActorHandle;
+use crate::game_module::actors::ai_controller::{AIController, AIProfileData};
+use crate::game_module::faction::FactionId;
 use crate::game_module::game_client::GameClient;
 use crate::game_module::ship::ship::{ShipInstance, ShipData};
 use crate::game_module::ship::ship_controller::{ ShipController };
@@ -28,43 +31,57 @@ pub enum MoveState {
 
 // ActorController
 pub struct ActorController {
-    pub _id: u64,
+    pub _actor_handle: ActorHandle,
     pub _actor_data: ActorData,
     pub _ship: ShipInstance,
     pub _action_state: ActionState,
     pub _move_state: MoveState,
-    pub _is_player_actor: bool
+    pub _is_player_actor: bool,
+    pub _faction_id: FactionId,
+    pub _ai_controller: AIController,
+    pub _is_dying: bool,
+    pub _dying_time: f32,
 }
 
 impl ActorController {
     pub fn create_actor_controller(
-        id: u64,
+        actor_handle: ActorHandle,
         ship_data: &RcRefCell<ShipData>,
         render_object: &RcRefCell<RenderObjectData>,
+        ai_profile_data: &RcRefCell<AIProfileData>,
+        faction_id: FactionId,
         is_player_actor: bool
     ) -> Rc<ActorController> {
+        let spawn_position = render_object.borrow()._transform_object.get_position().clone_owned();
         Rc::new(ActorController {
-            _id: id,
+            _actor_handle: actor_handle,
             _actor_data: ActorData {},
             _ship: ShipInstance::create_ship_instance(ship_data, render_object),
             _action_state: ActionState::Idle,
             _move_state: MoveState::Idle,
-            _is_player_actor: is_player_actor
+            _is_player_actor: is_player_actor,
+            _faction_id: faction_id,
+            _ai_controller: AIController::create_ai_controller(&spawn_position, ai_profile_data),
+            _is_dying: false,
+            _dying_time: 0.0,
         })
     }
 
     pub fn initialize_actor(&mut self, project_scene_manager: &mut ProjectSceneManager) {
-        self._ship.initialize_ship_instance(self, project_scene_manager);
+        self._ship.initialize_ship_instance(self._actor_handle, project_scene_manager);
     }
     pub fn remove_actor(&mut self, project_scene_manager: &mut ProjectSceneManager) {
         self._ship.remove_ship_instance(project_scene_manager);
     }
-    pub fn get_actor_id(&self) -> u64 {
-        self._id
+    pub fn get_actor_handle(&self) -> ActorHandle {
+        self._actor_handle
     }
     pub fn is_player_actor(&self) -> bool {
         self._is_player_actor
     }
+    pub fn get_faction_id(&self) -> FactionId {
+        self._faction_id
+    }
     pub fn get_actor_data(&self) -> &ActorData {
         &self._actor_data
     }
@@ -95,6 +112,19 @@ impl ActorController {
     pub fn get_velocity(&self) -> &Vector3<f32> {
         self.get_controller().get_velocity()
     }
+    pub fn get_ai_controller(&self) -> &AIController {
+        &self._ai_controller
+    }
+    pub fn get_ai_controller_mut(&mut self) -> &mut AIController {
+        &mut self._ai_controller
+    }
+    pub fn is_dying(&self) -> bool {
+        self._is_dying
+    }
+    // the actor manager removes the actor at the end of the frame
+    pub fn is_destruction_finished(&self) -> bool {
+        self._is_dying && self._ship.get_ship_data()._destruction_delay <= self._dying_time
+    }
 
     pub fn set_command_actor_fire(&mut self) {
         self._action_state = ActionState::Fire;
@@ -126,16 +156,33 @@ impl ActorController {
         }
     }
 
+    pub fn start_destruction(&mut self, game_client: &GameClient) {
+        self._is_dying = true;
+        self._dying_time = 0.0;
+        self.set_command_actor_idle();
+        self.set_command_actor_stop();
+        self._ship.play_destruction_effects(game_client);
+    }
+
     pub fn update_actor_controller(&mut self, game_client: &GameClient, delta_time: f32) {
-        if self._is_player_actor {
-            self.update_action_state(game_client, delta_time);
-            self.update_move_state(game_client, delta_time);
-        } else {
-            let ship_controller = ptr_as_mut(&self.get_ship()._controller);
-            ship_controller.set_velocity_yaw(1.0);
-            ship_controller.acceleration_forward(1.0);
+        if self.get_ship().is_destroyed() {
+            // the wreck drifts without any command until the removal
+            if false == self._is_dying {
+                self.start_destruction(game_client);
+            }
+            self._dying_time += delta_time;
+            self.get_ship_mut().update_ship(game_client, delta_time);
+            return;
         }
 
+        if false == self._is_player_actor {
+            let ai_controller = ptr_as_mut(&self._ai_controller);
+            ai_controller.update_ai_controller(game_client, self, delta_time);
+        }
+
+        self.update_action_state(game_client, delta_time);
+        self.update_move_state(game_client, delta_time);
+
         // update ship
         self.get_ship_mut().update_ship(game_client, delta_time);
     }
@@ -0,0 +1,314 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+use rust_engine_3d::utilities::math::TWO_PI;
+use rust_engine_3d::utilities::system::{RcRefCell, ptr_as_ref};
+use crate::game_module::actor_manager::ActorHandle;
+use crate::game_module::actors::actor::ActorController;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::{
+    AI_FIRE_ANGLE,
+    AI_FIRE_INTERVAL_MAX,
+    AI_FIRE_INTERVAL_MIN,
+    AI_FIRE_RANGE_RATIO,
+    AI_FIRE_TIME,
+    AI_LOST_TARGET_DISTANCE_RATIO,
+    AI_PATROL_ARRIVE_DISTANCE,
+    AI_STRAFE_SWITCH_TIME,
+    CHECK_TARGET_DISTANCE_MAX,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AIProfileData {
+    pub _aggression: f32,
+    pub _preferred_engagement_range: f32,
+    pub _reaction_time: f32,
+    pub _aim_error: f32,
+    pub _retreat_hull_threshold: f32,
+    pub _detect_distance: f32,
+    pub _patrol_radius: f32,
+    pub _turn_speed: f32,
+}
+
+impl Default for AIProfileData {
+    fn default() -> AIProfileData {
+        AIProfileData {
+            _aggression: 0.5,
+            _preferred_engagement_range: 40.0,
+            _reaction_time: 0.5,
+            _aim_error: 0.1,
+            _retreat_hull_threshold: 0.2,
+            _detect_distance: CHECK_TARGET_DISTANCE_MAX,
+            _patrol_radius: 50.0,
+            _turn_speed: 2.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AIState {
+    Patrol,
+    Acquire,
+    Approach,
+    Strafe,
+    Fire,
+    Retreat,
+}
+
+// orders of the player to the allied ships, the state machine runs again when the order is done
+#[derive(Clone, Debug, PartialEq)]
+pub enum AICommand {
+    None,
+    Move(Vector3<f32>),
+    Attack(ActorHandle),
+    Hold,
+}
+
+pub struct AIController {
+    pub _ai_profile_data: RcRefCell<AIProfileData>,
+    pub _ai_state: AIState,
+    pub _state_elapsed_time: f32,
+    pub _strafe_elapsed_time: f32,
+    pub _fire_cooldown_time: f32,
+    pub _target_actor_handle: Option<ActorHandle>,
+    pub _patrol_center: Vector3<f32>,
+    pub _patrol_position: Vector3<f32>,
+    pub _strafe_direction: f32,
+    pub _aim_error_yaw: f32,
+    pub _candidate_actor_handles: Vec<ActorHandle>,
+    pub _ai_command: AICommand,
+}
+
+impl AIController {
+    pub fn create_ai_controller(spawn_position: &Vector3<f32>, ai_profile_data: &RcRefCell<AIProfileData>) -> AIController {
+        AIController {
+            _ai_profile_data: ai_profile_data.clone(),
+            _ai_state: AIState::Patrol,
+            _state_elapsed_time: 0.0,
+            _strafe_elapsed_time: 0.0,
+            _fire_cooldown_time: 0.0,
+            _target_actor_handle: None,
+            _patrol_center: spawn_position.clone_owned(),
+            _patrol_position: spawn_position.clone_owned(),
+            _strafe_direction: 1.0,
+            _aim_error_yaw: 0.0,
+            _candidate_actor_handles: Vec::new(),
+            _ai_command: AICommand::None,
+        }
+    }
+
+    pub fn get_ai_profile_data(&self) -> &AIProfileData {
+        ptr_as_ref(self._ai_profile_data.as_ptr())
+    }
+
+    pub fn get_ai_state(&self) -> AIState {
+        self._ai_state
+    }
+
+    pub fn set_ai_state(&mut self, ai_state: AIState) {
+        if ai_state != self._ai_state {
+            self._ai_state = ai_state;
+            self._state_elapsed_time = 0.0;
+        }
+    }
+
+    pub fn get_ai_command(&self) -> &AICommand {
+        &self._ai_command
+    }
+
+    pub fn set_ai_command(&mut self, ai_command: AICommand) {
+        self._ai_command = ai_command;
+    }
+
+    pub fn get_target_actor<'a>(&self, game_client: &'a GameClient) -> Option<&'a ActorController> {
+        let target_actor = game_client.get_actor_manager().get_actor(self._target_actor_handle?)?;
+        if target_actor.get_ship().is_destroyed() { None } else { Some(target_actor) }
+    }
+
+    // nearest hostile actor inside of the detect distance
+    pub fn find_target_actor(&mut self, game_client: &GameClient, owner_actor: &ActorController, detect_distance: f32) -> Option<ActorHandle> {
+        let actor_manager = game_client.get_actor_manager_mut();
+        let owner_position = owner_actor.get_transform().get_position();
+        actor_manager.get_spatial_grid_mut().query_sphere(owner_position, detect_distance, &mut self._candidate_actor_handles);
+
+        let mut target_actor_handle: Option<ActorHandle> = None;
+        let mut target_distance = detect_distance;
+        for actor_handle in self._candidate_actor_handles.iter() {
+            if let Some(actor) = actor_manager.get_actor(*actor_handle) {
+                if actor_manager.is_hostile_actor(owner_actor, actor) && false == actor.get_ship().is_destroyed() {
+                    let distance = (actor.get_transform().get_position() - owner_position).norm();
+                    if distance < target_distance {
+                        target_distance = distance;
+                        target_actor_handle = Some(*actor_handle);
+                    }
+                }
+            }
+        }
+        target_actor_handle
+    }
+
+    pub fn pick_patrol_position(&mut self, patrol_radius: f32) {
+        let angle = rand::random::<f32>() * TWO_PI;
+        let radius = patrol_radius * rand::random::<f32>().sqrt();
+        self._patrol_position = &self._patrol_center + Vector3::new(angle.sin() * radius, 0.0, angle.cos() * radius);
+    }
+
+    // turns the ship at the turn speed and returns the remaining yaw to the goal direction
+    pub fn turn_to_direction(owner_actor: &mut ActorController, direction: &Vector3<f32>, yaw_offset: f32, turn_speed: f32, delta_time: f32) -> f32 {
+        // the forward of the ship is the negative front
+        let goal_yaw = (-direction.x).atan2(-direction.z) + yaw_offset;
+        let ship_controller = owner_actor.get_controller_mut();
+        let yaw = ship_controller.get_yaw();
+        let mut yaw_diff = (goal_yaw - yaw) % TWO_PI;
+        if std::f32::consts::PI < yaw_diff {
+            yaw_diff -= TWO_PI;
+        } else if yaw_diff < -std::f32::consts::PI {
+            yaw_diff += TWO_PI;
+        }
+        let max_turn = turn_speed * delta_time;
+        let turn = yaw_diff.max(-max_turn).min(max_turn);
+        ship_controller.set_yaw(yaw + turn);
+        yaw_diff - turn
+    }
+
+    pub fn update_ai_controller(&mut self, game_client: &GameClient, owner_actor: &mut ActorController, delta_time: f32) {
+        let ai_profile_data = ptr_as_ref(self._ai_profile_data.as_ptr());
+        let owner_position = owner_actor.get_transform().get_position().clone_owned();
+        self._state_elapsed_time += delta_time;
+        self._fire_cooldown_time = (self._fire_cooldown_time - delta_time).max(0.0);
+
+        // the move order ignores the enemies until the arrival
+        if let AICommand::Move(move_position) = &self._ai_command {
+            let to_move_position = move_position - &owner_position;
+            let move_distance = Vector3::new(to_move_position.x, 0.0, to_move_position.z).norm();
+            if move_distance < AI_PATROL_ARRIVE_DISTANCE {
+                self._patrol_center.clone_from(&owner_position);
+                self._patrol_position.clone_from(&owner_position);
+                self._ai_command = AICommand::None;
+            } else {
+                AIController::turn_to_direction(owner_actor, &(to_move_position / move_distance), 0.0, ai_profile_data._turn_speed, delta_time);
+                owner_actor.set_command_actor_walk();
+                return;
+            }
+        }
+
+        // the attack order keeps the target at any distance until it is destroyed
+        let mut is_attack_command = false;
+        if let AICommand::Attack(attack_actor_handle) = self._ai_command {
+            let is_attack_target_alive = match game_client.get_actor_manager().get_actor(attack_actor_handle) {
+                Some(attack_actor) => false == attack_actor.get_ship().is_destroyed(),
+                None => false,
+            };
+            if is_attack_target_alive {
+                is_attack_command = true;
+                self._target_actor_handle = Some(attack_actor_handle);
+                if AIState::Patrol == self._ai_state || AIState::Retreat == self._ai_state {
+                    self.set_ai_state(AIState::Acquire);
+                }
+            } else {
+                self._ai_command = AICommand::None;
+            }
+        }
+
+        // retreat has the priority over the other states
+        let ship = owner_actor.get_ship();
+        let hull_ratio = ship.get_hull_point() / ship.get_max_hull_point();
+        if AIState::Patrol != self._ai_state && false == is_attack_command && hull_ratio < ai_profile_data._retreat_hull_threshold {
+            self.set_ai_state(AIState::Retreat);
+        }
+
+        // lost target
+        let target_actor = self.get_target_actor(game_client);
+        let (to_target, target_distance) = match target_actor {
+            Some(target_actor) => {
+                let to_target = target_actor.get_transform().get_position() - &owner_position;
+                let target_distance = to_target.norm();
+                (to_target, target_distance)
+            },
+            None => (Vector3::zeros(), 0.0)
+        };
+        let lost_target_distance = ai_profile_data._detect_distance * AI_LOST_TARGET_DISTANCE_RATIO;
+        if AIState::Patrol != self._ai_state && (target_actor.is_none() || (false == is_attack_command && lost_target_distance < target_distance)) {
+            self._target_actor_handle = None;
+            self._patrol_center.clone_from(&owner_position);
+            self.pick_patrol_position(ai_profile_data._patrol_radius);
+            self.set_ai_state(AIState::Patrol);
+        }
+        let target_direction = if 0.0 < target_distance { to_target / target_distance } else { Vector3::zeros() };
+
+        match self._ai_state {
+            AIState::Patrol => {
+                let to_patrol_position = &self._patrol_position - &owner_position;
+                let patrol_distance = Vector3::new(to_patrol_position.x, 0.0, to_patrol_position.z).norm();
+                if patrol_distance < AI_PATROL_ARRIVE_DISTANCE {
+                    self.pick_patrol_position(ai_profile_data._patrol_radius);
+                } else {
+                    AIController::turn_to_direction(owner_actor, &(to_patrol_position / patrol_distance), 0.0, ai_profile_data._turn_speed, delta_time);
+                    owner_actor.set_command_actor_walk();
+                }
+
+                self._target_actor_handle = self.find_target_actor(game_client, owner_actor, ai_profile_data._detect_distance);
+                if self._target_actor_handle.is_some() {
+                    self.set_ai_state(AIState::Acquire);
+                }
+            },
+            AIState::Acquire => {
+                AIController::turn_to_direction(owner_actor, &target_direction, 0.0, ai_profile_data._turn_speed, delta_time);
+                if ai_profile_data._reaction_time <= self._state_elapsed_time {
+                    self.set_ai_state(AIState::Approach);
+                }
+            },
+            AIState::Approach => {
+                AIController::turn_to_direction(owner_actor, &target_direction, 0.0, ai_profile_data._turn_speed, delta_time);
+                if target_distance <= ai_profile_data._preferred_engagement_range {
+                    self.set_ai_state(AIState::Strafe);
+                } else {
+                    owner_actor.set_command_actor_walk();
+                }
+            },
+            AIState::Strafe | AIState::Fire => {
+                let remain_yaw = AIController::turn_to_direction(owner_actor, &target_direction, self._aim_error_yaw, ai_profile_data._turn_speed, delta_time);
+                // the strafe keeps its own timer, the bursts switch between strafe and fire
+                self._strafe_elapsed_time += delta_time;
+                if AI_STRAFE_SWITCH_TIME <= self._strafe_elapsed_time {
+                    self._strafe_direction = -self._strafe_direction;
+                    self._strafe_elapsed_time = 0.0;
+                }
+                owner_actor.get_controller_mut().acceleration_side(self._strafe_direction);
+
+                // the aggressive ships fire longer bursts with shorter pauses
+                let aggression = ai_profile_data._aggression.max(0.0).min(1.0);
+                let fire_interval = AI_FIRE_INTERVAL_MAX + (AI_FIRE_INTERVAL_MIN - AI_FIRE_INTERVAL_MAX) * aggression;
+                let fire_time = AI_FIRE_TIME * (0.5 + aggression);
+                // the ship keeps the engagement a little beyond the preferred range before approaching again
+                let fire_range = ai_profile_data._preferred_engagement_range * AI_FIRE_RANGE_RATIO;
+                if fire_range < target_distance {
+                    self.set_ai_state(AIState::Approach);
+                } else if AIState::Strafe == self._ai_state {
+                    if self._fire_cooldown_time <= 0.0 && remain_yaw.abs() < AI_FIRE_ANGLE {
+                        self._aim_error_yaw = (rand::random::<f32>() * 2.0 - 1.0) * ai_profile_data._aim_error;
+                        self.set_ai_state(AIState::Fire);
+                    }
+                } else {
+                    owner_actor.set_command_actor_fire();
+                    if fire_time <= self._state_elapsed_time {
+                        self._fire_cooldown_time = fire_interval;
+                        self.set_ai_state(AIState::Strafe);
+                    }
+                }
+            },
+            AIState::Retreat => {
+                AIController::turn_to_direction(owner_actor, &(-target_direction), 0.0, ai_profile_data._turn_speed, delta_time);
+                owner_actor.set_command_actor_walk();
+                owner_actor.get_controller_mut().boost_on();
+            },
+        }
+
+        // the hold order turns and fires in place
+        if AICommand::Hold == self._ai_command {
+            owner_actor.set_command_actor_stop();
+            owner_actor.get_controller_mut().acceleration_side(0.0);
+        }
+    }
+}
@@ -1 +1,2 @@
-pub mod actor;
\ No newline at end of file
+pub mod actor;
+pub mod ai_controller;
@@ -0,0 +1,257 @@
+use nalgebra::{Matrix3, Vector3};
+
+use rust_engine_3d::utilities::bounding_box::BoundingBox;
+
+
+#[derive(Clone, Debug)]
+pub struct CollisionHit {
+    pub _hit_ratio: f32,
+    pub _hit_position: Vector3<f32>,
+    pub _hit_normal: Vector3<f32>,
+}
+
+impl CollisionHit {
+    pub fn get_hit_rotation(&self) -> Vector3<f32> {
+        Vector3::new(0.0, self._hit_normal.x.atan2(self._hit_normal.z), 0.0)
+    }
+}
+
+pub fn intersect_segment_sphere(start: &Vector3<f32>, end: &Vector3<f32>, center: &Vector3<f32>, radius: f32) -> Option<CollisionHit> {
+    let segment = end - start;
+    let to_start = start - center;
+    let radius_squared = radius * radius;
+
+    // the segment begins inside of the sphere
+    if to_start.norm_squared() <= radius_squared {
+        let hit_normal = if 0.0 < to_start.norm_squared() { to_start.normalize() } else { -segment.normalize() };
+        return Some(CollisionHit {
+            _hit_ratio: 0.0,
+            _hit_position: center + hit_normal * radius,
+            _hit_normal: hit_normal,
+        });
+    }
+
+    let a = segment.norm_squared();
+    if a <= 0.0 {
+        return None;
+    }
+    let b = to_start.dot(&segment);
+    let c = to_start.norm_squared() - radius_squared;
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let hit_ratio = (-b - discriminant.sqrt()) / a;
+    if hit_ratio < 0.0 || 1.0 < hit_ratio {
+        return None;
+    }
+
+    let hit_position = start + segment * hit_ratio;
+    Some(CollisionHit {
+        _hit_normal: (&hit_position - center).normalize(),
+        _hit_position: hit_position,
+        _hit_ratio: hit_ratio,
+    })
+}
+
+// horizontal plane such as the sea surface, hit only from above
+pub fn intersect_segment_height_plane(start: &Vector3<f32>, end: &Vector3<f32>, height: f32) -> Option<CollisionHit> {
+    if start.y < height || height < end.y {
+        return None;
+    }
+
+    let segment_height = start.y - end.y;
+    let hit_ratio = if 0.0 < segment_height { (start.y - height) / segment_height } else { 0.0 };
+    Some(CollisionHit {
+        _hit_ratio: hit_ratio,
+        _hit_position: start + (end - start) * hit_ratio,
+        _hit_normal: Vector3::new(0.0, 1.0, 0.0),
+    })
+}
+
+// slab test against an axis aligned box
+pub fn intersect_segment_aabb(start: &Vector3<f32>, end: &Vector3<f32>, box_min: &Vector3<f32>, box_max: &Vector3<f32>) -> Option<CollisionHit> {
+    let segment = end - start;
+    let mut ratio_min: f32 = 0.0;
+    let mut ratio_max: f32 = 1.0;
+    let mut hit_normal: Vector3<f32> = Vector3::zeros();
+    for axis in 0..3 {
+        if segment[axis].abs() < f32::EPSILON {
+            if start[axis] < box_min[axis] || box_max[axis] < start[axis] {
+                return None;
+            }
+        } else {
+            let inv_direction = 1.0 / segment[axis];
+            let mut ratio_near = (box_min[axis] - start[axis]) * inv_direction;
+            let mut ratio_far = (box_max[axis] - start[axis]) * inv_direction;
+            let mut normal_sign: f32 = -1.0;
+            if ratio_far < ratio_near {
+                std::mem::swap(&mut ratio_near, &mut ratio_far);
+                normal_sign = 1.0;
+            }
+
+            if ratio_min < ratio_near {
+                ratio_min = ratio_near;
+                hit_normal = Vector3::zeros();
+                hit_normal[axis] = normal_sign;
+            }
+            ratio_max = ratio_max.min(ratio_far);
+            if ratio_max < ratio_min {
+                return None;
+            }
+        }
+    }
+
+    // the segment begins inside of the box
+    if hit_normal == Vector3::zeros() {
+        hit_normal = -segment.normalize();
+    }
+
+    Some(CollisionHit {
+        _hit_ratio: ratio_min,
+        _hit_position: start + segment * ratio_min,
+        _hit_normal: hit_normal,
+    })
+}
+
+// oriented box, the columns of the axes are the unit axes of the box
+pub fn intersect_segment_obb(start: &Vector3<f32>, end: &Vector3<f32>, center: &Vector3<f32>, axes: &Matrix3<f32>, half_extents: &Vector3<f32>) -> Option<CollisionHit> {
+    // the slab test in the space of the box
+    let to_local = axes.transpose();
+    let local_start = &to_local * (start - center);
+    let local_end = &to_local * (end - center);
+    let hit = intersect_segment_aabb(&local_start, &local_end, &(-half_extents), half_extents)?;
+    Some(CollisionHit {
+        _hit_ratio: hit._hit_ratio,
+        _hit_position: start + (end - start) * hit._hit_ratio,
+        _hit_normal: axes * hit._hit_normal,
+    })
+}
+
+pub fn get_nearest_collision_hit(lhs: Option<CollisionHit>, rhs: Option<CollisionHit>) -> Option<CollisionHit> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(if rhs._hit_ratio < lhs._hit_ratio { rhs } else { lhs }),
+        (lhs, None) => lhs,
+        (None, rhs) => rhs,
+    }
+}
+
+// Bounding sphere as the early out, then the box for the exact impact on the hull.
+// The engine refits the world bounds of a render object to its rotation, and the ships mostly turn around the up axis
+// with a small roll, so the axis aligned box stays close to the hull. intersect_segment_obb is there for a box with known local extents.
+pub fn intersect_segment_bound_box(start: &Vector3<f32>, end: &Vector3<f32>, bound_box: &BoundingBox) -> Option<CollisionHit> {
+    if intersect_segment_sphere(start, end, &bound_box._center, bound_box._radius).is_none() {
+        return None;
+    }
+    intersect_segment_aabb(start, end, &bound_box._min, &bound_box._max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_eq(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn segment_hits_sphere_surface() {
+        let hit = intersect_segment_sphere(&Vector3::new(-10.0, 0.0, 0.0), &Vector3::new(10.0, 0.0, 0.0), &Vector3::zeros(), 2.0).unwrap();
+        assert!(approx_eq(hit._hit_ratio, 0.4));
+        assert!(approx_eq(hit._hit_position.x, -2.0));
+        assert!(approx_eq(hit._hit_normal.x, -1.0));
+    }
+
+    #[test]
+    fn segment_misses_sphere() {
+        assert!(intersect_segment_sphere(&Vector3::new(-10.0, 5.0, 0.0), &Vector3::new(10.0, 5.0, 0.0), &Vector3::zeros(), 2.0).is_none());
+        // stops short of the sphere
+        assert!(intersect_segment_sphere(&Vector3::new(-10.0, 0.0, 0.0), &Vector3::new(-5.0, 0.0, 0.0), &Vector3::zeros(), 2.0).is_none());
+    }
+
+    #[test]
+    fn segment_starting_inside_sphere_hits_at_start() {
+        let hit = intersect_segment_sphere(&Vector3::new(1.0, 0.0, 0.0), &Vector3::new(10.0, 0.0, 0.0), &Vector3::zeros(), 2.0).unwrap();
+        assert!(approx_eq(hit._hit_ratio, 0.0));
+    }
+
+    #[test]
+    fn segment_hits_aabb_face() {
+        let box_min = Vector3::new(-1.0, -1.0, -1.0);
+        let box_max = Vector3::new(1.0, 1.0, 1.0);
+        let hit = intersect_segment_aabb(&Vector3::new(0.0, 5.0, 0.0), &Vector3::new(0.0, -5.0, 0.0), &box_min, &box_max).unwrap();
+        assert!(approx_eq(hit._hit_ratio, 0.4));
+        assert!(approx_eq(hit._hit_position.y, 1.0));
+        assert!(approx_eq(hit._hit_normal.y, 1.0));
+    }
+
+    #[test]
+    fn segment_misses_aabb() {
+        let box_min = Vector3::new(-1.0, -1.0, -1.0);
+        let box_max = Vector3::new(1.0, 1.0, 1.0);
+        // parallel to the x axis outside of the box
+        assert!(intersect_segment_aabb(&Vector3::new(-5.0, 2.0, 0.0), &Vector3::new(5.0, 2.0, 0.0), &box_min, &box_max).is_none());
+        // stops short of the box
+        assert!(intersect_segment_aabb(&Vector3::new(-5.0, 0.0, 0.0), &Vector3::new(-2.0, 0.0, 0.0), &box_min, &box_max).is_none());
+    }
+
+    #[test]
+    fn segment_hits_rotated_box() {
+        // rotated by 45 degrees around the up axis, the corner reaches sqrt(2) along the x and z axes
+        let angle = std::f32::consts::FRAC_PI_4;
+        let axes = Matrix3::new(
+            angle.cos(), 0.0, angle.sin(),
+            0.0, 1.0, 0.0,
+            -angle.sin(), 0.0, angle.cos()
+        );
+        let half_extents = Vector3::new(1.0, 1.0, 1.0);
+        let hit = intersect_segment_obb(&Vector3::new(-5.0, 0.0, 0.0), &Vector3::new(5.0, 0.0, 0.0), &Vector3::zeros(), &axes, &half_extents).unwrap();
+        assert!(approx_eq(hit._hit_position.x, -(2.0f32).sqrt()));
+        // passes beside the face of the axis aligned box, but hits the corner of the rotated box
+        let start = Vector3::new(-5.0, 0.0, 1.2);
+        let end = Vector3::new(5.0, 0.0, 1.2);
+        assert!(intersect_segment_aabb(&start, &end, &(-half_extents), &half_extents).is_none());
+        let hit = intersect_segment_obb(&start, &end, &Vector3::zeros(), &axes, &half_extents).unwrap();
+        assert!(approx_eq(hit._hit_position.z, 1.2));
+        assert!(approx_eq(hit._hit_normal.norm(), 1.0));
+    }
+
+    #[test]
+    fn segment_starting_inside_aabb_hits_at_start() {
+        let box_min = Vector3::new(-1.0, -1.0, -1.0);
+        let box_max = Vector3::new(1.0, 1.0, 1.0);
+        let hit = intersect_segment_aabb(&Vector3::new(0.5, 0.0, 0.0), &Vector3::new(5.0, 0.0, 0.0), &box_min, &box_max).unwrap();
+        assert!(approx_eq(hit._hit_ratio, 0.0));
+        assert!(approx_eq(hit._hit_position.x, 0.5));
+    }
+
+    #[test]
+    fn segment_hits_height_plane_only_from_above() {
+        let hit = intersect_segment_height_plane(&Vector3::new(0.0, 2.0, 0.0), &Vector3::new(0.0, -2.0, 0.0), 0.0).unwrap();
+        assert!(approx_eq(hit._hit_ratio, 0.5));
+        assert!(intersect_segment_height_plane(&Vector3::new(0.0, -2.0, 0.0), &Vector3::new(0.0, 2.0, 0.0), 0.0).is_none());
+    }
+
+    #[test]
+    fn nearest_collision_hit_prefers_smaller_ratio() {
+        let near = intersect_segment_height_plane(&Vector3::new(0.0, 1.0, 0.0), &Vector3::new(0.0, -3.0, 0.0), 0.0);
+        let far = intersect_segment_height_plane(&Vector3::new(0.0, 1.0, 0.0), &Vector3::new(0.0, -3.0, 0.0), -2.0);
+        let nearest = get_nearest_collision_hit(far, near).unwrap();
+        assert!(approx_eq(nearest._hit_ratio, 0.25));
+    }
+
+    #[test]
+    fn segment_hits_bound_box() {
+        let bound_box = BoundingBox {
+            _min: Vector3::new(-1.0, -1.0, -1.0),
+            _max: Vector3::new(1.0, 1.0, 1.0),
+            _center: Vector3::zeros(),
+            _radius: 3.0f32.sqrt(),
+            ..Default::default()
+        };
+        let hit = intersect_segment_bound_box(&Vector3::new(-5.0, 0.0, 0.0), &Vector3::new(5.0, 0.0, 0.0), &bound_box).unwrap();
+        assert!(approx_eq(hit._hit_position.x, -1.0));
+        assert!(intersect_segment_bound_box(&Vector3::new(-5.0, 3.0, 0.0), &Vector3::new(5.0, 3.0, 0.0), &bound_box).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use serde::{ Serialize, Deserialize };
+
+use rust_engine_3d::utilities::system::{RcRefCell, newRcRefCell};
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+
+pub type FactionId = usize;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum FactionRelation {
+    Hostile,
+    Neutral,
+    Allied,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct FactionData {
+    pub _faction_name: String,
+    pub _hud_color: [u32; 4],
+}
+
+impl Default for FactionData {
+    fn default() -> FactionData {
+        FactionData {
+            _faction_name: "".to_string(),
+            _hud_color: [255, 255, 255, 255],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct FactionRelationData {
+    pub _faction_name_a: String,
+    pub _faction_name_b: String,
+    pub _relation: FactionRelation,
+}
+
+impl Default for FactionRelationData {
+    fn default() -> FactionRelationData {
+        FactionRelationData {
+            _faction_name_a: "".to_string(),
+            _faction_name_b: "".to_string(),
+            _relation: FactionRelation::Hostile,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct FactionTableDataCreateInfo {
+    pub _factions: Vec<FactionData>,
+    pub _relations: Vec<FactionRelationData>,
+    pub _default_relation: FactionRelation,
+    pub _player_faction_name: String,
+    pub _non_player_faction_name: String,
+}
+
+impl Default for FactionTableDataCreateInfo {
+    fn default() -> FactionTableDataCreateInfo {
+        FactionTableDataCreateInfo {
+            _factions: vec![
+                FactionData {
+                    _faction_name: "player".to_string(),
+                    _hud_color: [0, 128, 255, 255],
+                },
+                FactionData {
+                    _faction_name: "enemy".to_string(),
+                    _hud_color: [255, 0, 0, 255],
+                },
+            ],
+            _relations: Vec::new(),
+            _default_relation: FactionRelation::Hostile,
+            _player_faction_name: "player".to_string(),
+            _non_player_faction_name: "enemy".to_string(),
+        }
+    }
+}
+
+pub struct FactionTableData {
+    pub _faction_table_name: String,
+    pub _factions: Vec<FactionData>,
+    pub _faction_ids: HashMap<String, FactionId>,
+    // relation of every pair of factions, indexed by faction_a * faction count + faction_b
+    pub _relations: Vec<FactionRelation>,
+    pub _player_faction_id: FactionId,
+    pub _non_player_faction_id: FactionId,
+}
+
+impl FactionTableData {
+    pub fn create_faction_table_data(faction_table_name: &str, create_info: &FactionTableDataCreateInfo) -> RcRefCell<FactionTableData> {
+        let faction_count = create_info._factions.len();
+        let mut faction_ids: HashMap<String, FactionId> = HashMap::new();
+        for (faction_id, faction_data) in create_info._factions.iter().enumerate() {
+            faction_ids.insert(faction_data._faction_name.clone(), faction_id);
+        }
+
+        // a faction is always allied with itself
+        let mut relations: Vec<FactionRelation> = vec![create_info._default_relation; faction_count * faction_count];
+        for faction_id in 0..faction_count {
+            relations[faction_id * faction_count + faction_id] = FactionRelation::Allied;
+        }
+        for relation_data in create_info._relations.iter() {
+            let faction_a = faction_ids.get(&relation_data._faction_name_a);
+            let faction_b = faction_ids.get(&relation_data._faction_name_b);
+            if let (Some(faction_a), Some(faction_b)) = (faction_a, faction_b) {
+                relations[faction_a * faction_count + faction_b] = relation_data._relation;
+                relations[faction_b * faction_count + faction_a] = relation_data._relation;
+            } else {
+                log::error!("{}: unknown faction relation {} - {}", faction_table_name, relation_data._faction_name_a, relation_data._faction_name_b);
+            }
+        }
+
+        let player_faction_id = *faction_ids.get(&create_info._player_faction_name).unwrap_or(&0);
+        let non_player_faction_id = *faction_ids.get(&create_info._non_player_faction_name).unwrap_or(&0);
+        newRcRefCell(FactionTableData {
+            _faction_table_name: faction_table_name.to_string(),
+            _factions: create_info._factions.clone(),
+            _faction_ids: faction_ids,
+            _relations: relations,
+            _player_faction_id: player_faction_id,
+            _non_player_faction_id: non_player_faction_id,
+        })
+    }
+
+    pub fn get_faction_count(&self) -> usize {
+        self._factions.len()
+    }
+
+    // an empty faction name falls back to the default faction of the player or the non player
+    pub fn get_faction_id(&self, faction_name: &str, is_player_actor: bool) -> FactionId {
+        if let Some(faction_id) = self._faction_ids.get(faction_name) {
+            return *faction_id;
+        }
+        if false == faction_name.is_empty() {
+            log::error!("{}: unknown faction {}", self._faction_table_name, faction_name);
+        }
+        if is_player_actor { self._player_faction_id } else { self._non_player_faction_id }
+    }
+
+    pub fn get_faction_name(&self, faction_id: FactionId) -> &str {
+        &self._factions[faction_id]._faction_name
+    }
+
+    pub fn get_relation(&self, faction_a: FactionId, faction_b: FactionId) -> FactionRelation {
+        self._relations[faction_a * self.get_faction_count() + faction_b]
+    }
+
+    pub fn is_hostile(&self, faction_a: FactionId, faction_b: FactionId) -> bool {
+        FactionRelation::Hostile == self.get_relation(faction_a, faction_b)
+    }
+
+    pub fn get_hud_color(&self, faction_id: FactionId) -> u32 {
+        let color = &self._factions[faction_id]._hud_color;
+        get_color32(color[0], color[1], color[2], color[3])
+    }
+
+    // color of the faction as seen from the viewer, such as the player
+    pub fn get_relation_color(&self, viewer_faction_id: FactionId, faction_id: FactionId) -> u32 {
+        match self.get_relation(viewer_faction_id, faction_id) {
+            FactionRelation::Hostile => self.get_hud_color(faction_id),
+            FactionRelation::Neutral => get_color32(255, 255, 0, 255),
+            FactionRelation::Allied => get_color32(0, 255, 0, 255),
+        }
+    }
+}
@@ -9,7 +9,11 @@ use crate::application::project_scene_manager::ProjectSceneManager;
 use crate::game_module::actor_manager::ActorManager;
 use crate::game_module::game_constants::SCROLL_DELTA_TO_CAMERA_DISTANCE_SPEED;
 use crate::game_module::game_controller::{GameViewMode, GameController};
+use crate::game_module::game_event_manager::GameEventManager;
+use crate::game_module::game_states::game_state::{GameStateManager, GameStateType};
 use crate::game_module::game_ui::GameUIManager;
+use crate::game_module::objective_manager::ObjectiveManager;
+use crate::game_module::spawn_manager::SpawnManager;
 use crate::game_module::weapon_manager::WeaponManager;
 use crate::resource::project_resource::ProjectResources;
 use crate::renderer::project_ui::ProjectUIManager;
@@ -25,7 +29,11 @@ pub struct GameClient {
     pub _effect_manager: *const EffectManager,
     pub _actor_manager: Box<ActorManager>,
     pub _game_controller: Box<GameController>,
+    pub _game_event_manager: Box<GameEventManager>,
     pub _game_ui_manager: Box<GameUIManager>,
+    pub _game_state_manager: Box<GameStateManager>,
+    pub _objective_manager: Box<ObjectiveManager>,
+    pub _spawn_manager: Box<SpawnManager>,
     pub _weapon_manager: Box<WeaponManager>
 }
 
@@ -40,7 +48,11 @@ impl GameClient {
             _effect_manager: std::ptr::null(),
             _actor_manager: ActorManager::create_actor_manager(),
             _game_controller: GameController::create_game_controller(),
+            _game_event_manager: GameEventManager::create_game_event_manager(),
             _game_ui_manager: GameUIManager::create_game_ui_manager(),
+            _game_state_manager: GameStateManager::create_game_state_manager(),
+            _objective_manager: ObjectiveManager::create_objective_manager(),
+            _spawn_manager: SpawnManager::create_spawn_manager(),
             _weapon_manager: WeaponManager::create_weapon_manager(),
         })
     }
@@ -54,16 +66,24 @@ impl GameClient {
         self._project_ui_manager = project_application.get_project_ui_manager();
         self._audio_manager = project_application.get_audio_manager();
         self._effect_manager = project_application.get_effect_manager();
+        self._game_event_manager.initialize_game_event_manager(game_client);
         self._game_ui_manager.initialize_game_ui_manager(game_client);
         self._game_controller.initialize_game_controller(game_client);
         self._actor_manager.initialize_actor_manager(game_client);
         self._weapon_manager.initialize_weapon_manager(game_client);
+        self._objective_manager.initialize_objective_manager(game_client);
+        self._spawn_manager.initialize_spawn_manager(game_client);
+        self._game_state_manager.initialize_game_state_manager(game_client);
     }
 
     pub fn destroy_game_client(&mut self) {
+        self._game_state_manager.destroy_game_state_manager();
+        self._objective_manager.destroy_objective_manager();
+        self._spawn_manager.destroy_spawn_manager();
         self._weapon_manager.destroy_weapon_manager();
         self._actor_manager.destroy_actor_manager();
         self._game_ui_manager.destroy_game_ui_manager();
+        self._game_event_manager.destroy_game_event_manager();
     }
 
     pub fn get_project_application(&self) -> &ProjectApplication { ptr_as_ref(self._project_application) }
@@ -82,17 +102,47 @@ impl GameClient {
     pub fn get_actor_manager_mut(&self) -> &mut ActorManager { ptr_as_mut(self._actor_manager.as_ref()) }
     pub fn get_game_controller(&self) -> &GameController { ptr_as_ref(self._game_controller.as_ref()) }
     pub fn get_game_controller_mut(&self) -> &mut GameController { ptr_as_mut(self._game_controller.as_ref()) }
+    pub fn get_game_event_manager(&self) -> &GameEventManager { ptr_as_ref(self._game_event_manager.as_ref()) }
+    pub fn get_game_event_manager_mut(&self) -> &mut GameEventManager { ptr_as_mut(self._game_event_manager.as_ref()) }
     pub fn get_game_ui_manager(&self) -> &GameUIManager { ptr_as_ref(self._game_ui_manager.as_ref()) }
     pub fn get_game_ui_manager_mut(&self) -> &mut GameUIManager { ptr_as_mut(self._game_ui_manager.as_ref()) }
     pub fn get_weapon_manager(&self) -> &WeaponManager { ptr_as_ref(self._weapon_manager.as_ref()) }
     pub fn get_weapon_manager_mut(&self) -> &mut WeaponManager { ptr_as_mut(self._weapon_manager.as_ref()) }
+    pub fn get_objective_manager(&self) -> &ObjectiveManager { ptr_as_ref(self._objective_manager.as_ref()) }
+    pub fn get_objective_manager_mut(&self) -> &mut ObjectiveManager { ptr_as_mut(self._objective_manager.as_ref()) }
+    pub fn get_spawn_manager(&self) -> &SpawnManager { ptr_as_ref(self._spawn_manager.as_ref()) }
+    pub fn get_spawn_manager_mut(&self) -> &mut SpawnManager { ptr_as_mut(self._spawn_manager.as_ref()) }
+    pub fn get_game_state_manager(&self) -> &GameStateManager { ptr_as_ref(self._game_state_manager.as_ref()) }
+    pub fn get_game_state_manager_mut(&self) -> &mut GameStateManager { ptr_as_mut(self._game_state_manager.as_ref()) }
 
     pub fn start_game(&mut self) {
-        self.get_project_scene_manager_mut().open_scene_data("default");
+        self._game_state_manager.reset_state(GameStateType::Boot);
+    }
+
+    pub fn start_mission(&self, scene_name: &str) {
+        self.get_project_scene_manager_mut().open_scene_data(scene_name);
         self.get_actor_manager_mut().spawn_actors();
+        let level_data = self.get_project_scene_manager().get_level_data();
+        self.get_spawn_manager_mut().start_spawners(level_data);
+        self.get_objective_manager_mut().start_objectives(&level_data._objectives);
+    }
+
+    pub fn end_mission(&self) {
+        self.get_objective_manager_mut().clear_objectives();
+        self.get_spawn_manager_mut().clear_spawners();
+        let project_scene_manager = self.get_project_scene_manager_mut();
+        self.get_actor_manager_mut().remove_all_actors(project_scene_manager);
+        // the bullets refer to the pooled render objects of the scene
+        self.get_weapon_manager_mut().destroy_weapon_manager();
+        project_scene_manager.close_scene_data();
+        self.get_game_event_manager_mut().clear_events();
     }
 
     pub fn update_event(&mut self) {
+        self._game_state_manager.update_event();
+    }
+
+    pub fn update_mission_event(&self) {
         let project_application = ptr_as_ref(self._project_application);
         let engine_application = project_application.get_engine_application();
         let project_scene_manager = ptr_as_ref(self._project_scene_manager);
@@ -107,22 +157,56 @@ impl GameClient {
         let pressed_key_c = keyboard_input_data.get_key_pressed(VirtualKeyCode::C);
 
         let main_camera = project_scene_manager.get_main_camera_mut();
-        let player_actor = ptr_as_mut(self.get_actor_manager().get_player_actor());
+        let game_controller = self.get_game_controller_mut();
 
-        if 0 != mouse_move_data._mouse_pos_delta.x || 0 != mouse_move_data._mouse_pos_delta.y || 0 != scroll_delta.y || keyboard_input_data.is_any_key_hold() {
-            self._game_controller.update_target_position(project_scene_manager, main_camera, &mouse_move_data._mouse_pos);
+        if 0 != mouse_move_data._mouse_pos_delta.x || 0 != mouse_move_data._mouse_pos_delta.y {
+            self.get_game_ui_manager_mut().set_crosshair_pos(&mouse_move_data._mouse_pos);
         }
 
         if 0 != scroll_delta.y {
-            self._game_controller.update_camera_distance(-scroll_delta.y as f32 * SCROLL_DELTA_TO_CAMERA_DISTANCE_SPEED);
+            game_controller.update_camera_distance(-scroll_delta.y as f32 * SCROLL_DELTA_TO_CAMERA_DISTANCE_SPEED);
         }
 
         if pressed_key_c {
-            self._game_controller.toggle_view_mode();
+            game_controller.toggle_view_mode();
+        }
+
+        if GameViewMode::TopViewMode == game_controller._game_view_mode {
+            game_controller.update_camera_edge_scroll(
+                &engine_application._window_size,
+                &mouse_move_data,
+                &mouse_input_data,
+                &mouse_delta,
+                time_data._delta_time as f32
+            );
+        }
+
+        // the right button frees the crosshair of the chase view from the center
+        if GameViewMode::ChaseViewMode == game_controller._game_view_mode {
+            self.get_game_ui_manager_mut().set_crosshair_tracking_mouse(mouse_input_data._btn_r_hold);
         }
 
-        match self._game_controller._game_view_mode {
-            GameViewMode::SideViewMode => self._game_controller.update_event_for_side_view_mode(
+        // the camera moves every frame, so the target is updated even if the mouse stays
+        let crosshair_pos = self.get_game_ui_manager().get_crosshair_screen_pos();
+        game_controller.update_target_position(project_scene_manager, main_camera, &crosshair_pos);
+
+        game_controller.update_locked_target(&keyboard_input_data);
+
+        // the allied ships are commanded in the top view even while the player is dead
+        if GameViewMode::TopViewMode == game_controller._game_view_mode {
+            game_controller.update_selection_event(&keyboard_input_data, &mouse_input_data, main_camera, &engine_application._window_size);
+        }
+
+        // the player can not control the wreck while waiting for the respawn
+        let player_actor = match self.get_actor_manager().get_player_actor_mut() {
+            Some(player_actor) if false == player_actor.get_ship().is_destroyed() => player_actor,
+            _ => return,
+        };
+
+        // the weapons aim at the target position under the crosshair
+        player_actor.get_ship_mut().set_aim_position(Some(game_controller.get_target_position()));
+        match game_controller._game_view_mode {
+            GameViewMode::SideViewMode => game_controller.update_event_for_side_view_mode(
                 time_data,
                 joystick_input_data,
                 &keyboard_input_data,
@@ -132,15 +216,34 @@ impl GameClient {
                 main_camera,
                 player_actor
             ),
+            GameViewMode::TopViewMode => game_controller.update_event_for_top_view_mode(
+                joystick_input_data,
+                &keyboard_input_data,
+                player_actor
+            ),
+            GameViewMode::ChaseViewMode => game_controller.update_event_for_chase_view_mode(
+                joystick_input_data,
+                &keyboard_input_data,
+                &mouse_input_data,
+                &mouse_delta,
+                player_actor
+            ),
             _ => assert!(false, "Not implemented."),
         };
     }
 
+    pub fn update_mission(&self, delta_time: f32) {
+        self.get_game_event_manager_mut().update_game_event_manager();
+        self.get_game_controller_mut().update_game_controller(delta_time);
+        self.get_spawn_manager_mut().update_spawn_manager(delta_time);
+        self.get_actor_manager_mut().update_actor_manager(delta_time);
+        self.get_weapon_manager_mut().update_weapon_manager(delta_time);
+        self.get_objective_manager_mut().update_objective_manager(delta_time);
+        self.get_game_ui_manager_mut().update_game_ui(delta_time);
+    }
+
     pub fn update_game_client(&mut self) {
         let delta_time = self.get_project_application().get_engine_application()._time_data._delta_time as f32;
-        self._game_controller.update_game_controller(delta_time);
-        self._actor_manager.update_actor_manager(delta_time);
-        self._weapon_manager.update_weapon_manager(delta_time);
-        self._game_ui_manager.update_game_ui(delta_time);
+        self._game_state_manager.update_game_state_manager(delta_time);
     }
 }
\ No newline at end of file
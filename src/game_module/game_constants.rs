@@ -2,6 +2,16 @@ pub const GRAVITY: f32 = 9.8;
 
 pub const CHECK_TARGET_DISTANCE_MAX: f32 = 100.0;
 
+pub const AI_STRAFE_SWITCH_TIME: f32 = 2.0;
+// the longest pause is shorter than the strafe switch, so even a calm ship fires on every strafe
+pub const AI_FIRE_INTERVAL_MIN: f32 = 0.5;
+pub const AI_FIRE_INTERVAL_MAX: f32 = 1.5;
+pub const AI_FIRE_TIME: f32 = 1.0;
+pub const AI_FIRE_RANGE_RATIO: f32 = 1.5;
+pub const AI_FIRE_ANGLE: f32 = 0.2;
+pub const AI_PATROL_ARRIVE_DISTANCE: f32 = 5.0;
+pub const AI_LOST_TARGET_DISTANCE_RATIO: f32 = 1.5;
+
 pub const FIRE_PITCH_MIN: f32 = -0.75;
 pub const FIRE_PITCH_MAX: f32 = 0.75;
 
@@ -11,4 +21,36 @@ pub const CAMERA_DISTANCE_MAX: f32 = 100.0;
 pub const CAMERA_DISTANCE_SPEED: f32 = 5.0;
 pub const CAMERA_VERTICAL_OFFSET: f32 = 20.0;
 pub const CAMERA_EDGE_SCROLL_SPEED: f32 = 100.0;
-pub const CAMERA_EDGE_SCROLL_SPEED_BY_MOUSE: f32 = 0.05;
\ No newline at end of file
+pub const CAMERA_EDGE_SCROLL_SPEED_BY_MOUSE: f32 = 0.05;
+pub const CAMERA_EDGE_SCROLL_MARGIN: i32 = 20;
+pub const AIM_RAY_DISTANCE: f32 = 1000.0;
+pub const TOP_VIEW_CAMERA_PITCH: f32 = -std::f32::consts::PI * 0.5;
+pub const CHASE_CAMERA_DISTANCE_RATIO: f32 = 0.5;
+pub const CHASE_CAMERA_VERTICAL_OFFSET: f32 = 5.0;
+pub const CHASE_CAMERA_SPRING_SPEED: f32 = 8.0;
+pub const CHASE_CAMERA_ROTATION_SPEED: f32 = 0.005;
+
+pub const GATLING_MIN_FIRE_RATE_RATIO: f32 = 0.2;
+// one number key per fire group
+pub const MAX_WEAPON_GROUP_COUNT: usize = 9;
+
+pub const SPATIAL_GRID_CELL_SIZE: f32 = 20.0;
+
+// the bullet pool grows in chunks up to this count, the shots beyond it are dropped
+pub const MAX_BULLET_COUNT: usize = 1024;
+pub const BULLET_POOL_ALLOCATION_COUNT: usize = 64;
+// the beam of a hitscan shot stays until the next shot, but at least this long
+pub const HITSCAN_BEAM_MIN_TIME: f32 = 0.05;
+
+pub const MISSION_SCENE_NAME: &str = "default";
+// the spawner spawning forever without an interval would flood the mission
+pub const MIN_INFINITE_SPAWNER_INTERVAL: f32 = 1.0;
+pub const MAX_OBJECTIVE_HUD_COUNT: usize = 8;
+pub const TARGET_HUD_OFFSET: f32 = 20.0;
+pub const OBJECTIVE_MESSAGE_TIME: f32 = 3.0;
+pub const PLAYER_HIT_FLASH_TIME: f32 = 0.2;
+
+pub const DEFAULT_PLAYER_LIVES: u32 = 3;
+pub const PLAYER_RESPAWN_TIME: f32 = 3.0;
+pub const DEATH_CAMERA_DISTANCE_SPEED: f32 = 10.0;
+pub const DEATH_CAMERA_HEIGHT_SPEED: f32 = 5.0;
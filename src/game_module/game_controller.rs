@@ -1,28 +1,60 @@
-use nalgebra::{Vector2, Vector3};
+use nalgebra::{Vector2, Vector3, Vector4};
 use winit::event::VirtualKeyCode;
 
 use rust_engine_3d::application::application::TimeData;
 use rust_engine_3d::application::input::{KeyboardInputData, MouseMoveData, MouseInputData, JoystickInputData, ButtonState};
 use rust_engine_3d::application::scene_manager::ProjectSceneManagerBase;
 use rust_engine_3d::renderer::camera::CameraObjectData;
+use rust_engine_3d::utilities::bounding_box::BoundingBox;
 use rust_engine_3d::utilities::math;
 use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
 use crate::application::project_scene_manager::ProjectSceneManager;
+use crate::game_module::actor_manager::ActorHandle;
 use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actors::ai_controller::AICommand;
+use crate::game_module::collision::{CollisionHit, intersect_segment_bound_box};
 use crate::game_module::game_constants::{
+    AIM_RAY_DISTANCE,
     CAMERA_DISTANCE_MIN,
     CAMERA_DISTANCE_MAX,
     CAMERA_DISTANCE_SPEED,
-    CAMERA_VERTICAL_OFFSET
+    CAMERA_EDGE_SCROLL_MARGIN,
+    CAMERA_EDGE_SCROLL_SPEED,
+    CAMERA_EDGE_SCROLL_SPEED_BY_MOUSE,
+    CAMERA_VERTICAL_OFFSET,
+    CHASE_CAMERA_DISTANCE_RATIO,
+    CHASE_CAMERA_ROTATION_SPEED,
+    CHASE_CAMERA_SPRING_SPEED,
+    CHASE_CAMERA_VERTICAL_OFFSET,
+    DEATH_CAMERA_DISTANCE_SPEED,
+    DEATH_CAMERA_HEIGHT_SPEED,
+    FIRE_PITCH_MAX,
+    FIRE_PITCH_MIN,
+    MAX_WEAPON_GROUP_COUNT,
+    TOP_VIEW_CAMERA_PITCH,
 };
 use crate::game_module::game_client::GameClient;
 use crate::game_module::game_ui::GameUIManager;
 
 
+const WEAPON_GROUP_KEYS: [VirtualKeyCode; MAX_WEAPON_GROUP_COUNT] = [
+    VirtualKeyCode::Key1,
+    VirtualKeyCode::Key2,
+    VirtualKeyCode::Key3,
+    VirtualKeyCode::Key4,
+    VirtualKeyCode::Key5,
+    VirtualKeyCode::Key6,
+    VirtualKeyCode::Key7,
+    VirtualKeyCode::Key8,
+    VirtualKeyCode::Key9,
+];
+
 #[repr(i32)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GameViewMode {
     SideViewMode,
+    TopViewMode,
+    ChaseViewMode,
     Count
 }
 
@@ -34,6 +66,19 @@ pub struct GameController {
     pub _target_position: Vector3<f32>,
     pub _target_direction: Vector3<f32>,
     pub _relative_target_position: Vector3<f32>,
+    // actor under the crosshair
+    pub _target_actor_handle: Option<ActorHandle>,
+    // hostile actor shown by the target hud
+    pub _locked_target_actor_handle: Option<ActorHandle>,
+    // allied ships selected by the selection area of the top view
+    pub _selected_actor_handles: Vec<ActorHandle>,
+    // offset of the top view camera from the player, moved by the edge scrolling
+    pub _camera_pan_offset: Vector3<f32>,
+    pub _side_view_camera_pitch: f32,
+    // spring arm of the chase camera, the yaw follows the player and the pitch follows the mouse
+    pub _chase_camera_position: Vector3<f32>,
+    pub _chase_camera_yaw: f32,
+    pub _chase_camera_pitch: f32,
     pub _game_view_mode: GameViewMode,
 }
 
@@ -48,6 +93,14 @@ impl GameController {
             _target_position: Vector3::zeros(),
             _target_direction: Vector3::zeros(),
             _relative_target_position: Vector3::zeros(),
+            _target_actor_handle: None,
+            _locked_target_actor_handle: None,
+            _selected_actor_handles: Vec::new(),
+            _camera_pan_offset: Vector3::zeros(),
+            _side_view_camera_pitch: 0.0,
+            _chase_camera_position: Vector3::zeros(),
+            _chase_camera_yaw: 0.0,
+            _chase_camera_pitch: 0.0,
             _game_view_mode: GameViewMode::SideViewMode,
         })
     }
@@ -71,9 +124,29 @@ impl GameController {
         if target_view_mode == self._game_view_mode { true } else { false }
     }
     pub fn change_view_mode(&mut self, view_mode: GameViewMode) {
+        // the crosshair follows the mouse in the mouse aimed view modes, the chase view tracks it only while free aiming
+        let is_tracking_mouse = match view_mode {
+            GameViewMode::SideViewMode | GameViewMode::TopViewMode => true,
+            _ => false,
+        };
+        self.get_game_ui_manager_mut().show_selection_area(GameViewMode::TopViewMode == view_mode);
+        self.get_game_ui_manager_mut().set_crosshair_tracking_mouse(is_tracking_mouse);
+        self._camera_pan_offset = Vector3::zeros();
+
+        // the other view modes override the pitch of the camera, so restore the pitch of the scene when returning
         let is_side_view_mode = GameViewMode::SideViewMode == view_mode;
-        self.get_game_ui_manager_mut().show_selection_area(false == is_side_view_mode);
-        self.get_game_ui_manager_mut().set_crosshair_tracking_mouse(is_side_view_mode);
+        let was_side_view_mode = GameViewMode::SideViewMode == self._game_view_mode;
+        if was_side_view_mode && false == is_side_view_mode {
+            self._side_view_camera_pitch = self.get_main_camera()._transform_object.get_pitch();
+        } else if is_side_view_mode && false == was_side_view_mode {
+            self.get_main_camera_mut()._transform_object.set_pitch(self._side_view_camera_pitch);
+        }
+
+        // the spring arm starts from the previous camera
+        if GameViewMode::ChaseViewMode == view_mode {
+            self._chase_camera_position.clone_from(self.get_main_camera()._transform_object.get_position());
+            self._chase_camera_pitch = 0.0;
+        }
         self._game_view_mode = view_mode;
     }
     pub fn toggle_view_mode(&mut self) {
@@ -91,11 +164,255 @@ impl GameController {
             self._camera_goal_distance = CAMERA_DISTANCE_MAX;
         }
     }
-    pub fn update_target_position(&mut self, _project_scene_manager: &ProjectSceneManager, _main_camera: &CameraObjectData, _mouse_pos: &Vector2<i32>) {
-        // todo
-        // let relative_pos = main_camera.convert_screen_to_relative_world(mouse_pos);
-        // self._target_direction = self._target_position.normalize();
-        // self._relative_target_position = self._target_position - main_camera._transform_object.get_position();
+    pub fn get_target_position(&self) -> &Vector3<f32> { &self._target_position }
+    pub fn get_target_actor_handle(&self) -> Option<ActorHandle> { self._target_actor_handle }
+    pub fn get_locked_target_actor_handle(&self) -> Option<ActorHandle> { self._locked_target_actor_handle }
+
+    pub fn intersect_ray_plane(ray_origin: &Vector3<f32>, ray_direction: &Vector3<f32>, plane_position: &Vector3<f32>, plane_normal: &Vector3<f32>) -> Option<Vector3<f32>> {
+        let denominator = ray_direction.dot(plane_normal);
+        if denominator.abs() < f32::EPSILON {
+            return None;
+        }
+        let ray_distance = (plane_position - ray_origin).dot(plane_normal) / denominator;
+        if ray_distance < 0.0 {
+            return None;
+        }
+        Some(ray_origin + ray_direction * ray_distance)
+    }
+
+    // The ray from the camera through the crosshair hits the nearest actor, otherwise the gameplay plane of the view mode,
+    // the plane of the side scroller, the plane of the ship seen from the top or the sea, otherwise the far point of the ray.
+    pub fn update_target_position(&mut self, project_scene_manager: &ProjectSceneManager, main_camera: &CameraObjectData, screen_pos: &Vector2<i32>) {
+        let actor_manager = self.get_game_client().get_actor_manager();
+        let ray_origin = main_camera._transform_object.get_position().clone_owned();
+        let ray_direction = main_camera.convert_screen_to_relative_world(screen_pos).normalize();
+        let ray_end = &ray_origin + &ray_direction * AIM_RAY_DISTANCE;
+
+        let mut target_actor_handle: Option<ActorHandle> = None;
+        let mut target_hit: Option<CollisionHit> = None;
+        for actor in actor_manager.get_actors() {
+            if actor.is_player_actor() || actor.is_dying() {
+                continue;
+            }
+            if let Some(hit) = intersect_segment_bound_box(&ray_origin, &ray_end, actor.get_bound_box()) {
+                let is_nearest = match target_hit.as_ref() {
+                    Some(nearest_hit) => hit._hit_ratio < nearest_hit._hit_ratio,
+                    None => true,
+                };
+                if is_nearest {
+                    target_actor_handle = Some(actor.get_actor_handle());
+                    target_hit = Some(hit);
+                }
+            }
+        }
+
+        let player_position = match actor_manager.get_player_position() {
+            Some(player_position) => player_position.clone_owned(),
+            None => ray_origin.clone_owned(),
+        };
+        let target_position = match target_hit {
+            Some(target_hit) => Some(target_hit._hit_position),
+            None => match self._game_view_mode {
+                GameViewMode::SideViewMode => GameController::intersect_ray_plane(&ray_origin, &ray_direction, &player_position, &Vector3::new(0.0, 0.0, 1.0)),
+                GameViewMode::TopViewMode => GameController::intersect_ray_plane(&ray_origin, &ray_direction, &player_position, &Vector3::new(0.0, 1.0, 0.0)),
+                _ => {
+                    let sea_position = Vector3::new(0.0, project_scene_manager.get_sea_height(), 0.0);
+                    GameController::intersect_ray_plane(&ray_origin, &ray_direction, &sea_position, &Vector3::new(0.0, 1.0, 0.0))
+                },
+            },
+        };
+
+        self._target_actor_handle = target_actor_handle;
+        self._target_position = target_position.unwrap_or(ray_end);
+        self._relative_target_position = &self._target_position - &player_position;
+        self._target_direction = if 0.0 < self._relative_target_position.norm_squared() { self._relative_target_position.normalize() } else { Vector3::zeros() };
+    }
+
+    // The nearest hostile actor is locked when the lock is lost, and T cycles to the next hostile actor by distance.
+    pub fn update_locked_target(&mut self, keyboard_input_data: &KeyboardInputData) {
+        let actor_manager = ptr_as_ref(self._game_client).get_actor_manager();
+        let player_actor = match actor_manager.get_player_actor() {
+            Some(player_actor) if false == player_actor.is_dying() => player_actor,
+            _ => {
+                self._locked_target_actor_handle = None;
+                return;
+            }
+        };
+
+        let player_position = player_actor.get_transform().get_position();
+        let mut hostile_actors: Vec<(f32, ActorHandle)> = actor_manager.get_actors()
+            .filter(|actor| false == actor.is_dying() && actor_manager.is_hostile_actor(player_actor, actor))
+            .map(|actor| ((actor.get_transform().get_position() - player_position).norm_squared(), actor.get_actor_handle()))
+            .collect();
+        if hostile_actors.is_empty() {
+            self._locked_target_actor_handle = None;
+            return;
+        }
+        hostile_actors.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let locked_index = self._locked_target_actor_handle.and_then(|locked_actor_handle| {
+            hostile_actors.iter().position(|(_, actor_handle)| locked_actor_handle == *actor_handle)
+        });
+        let target_index = if keyboard_input_data.get_key_pressed(VirtualKeyCode::T) {
+            locked_index.map_or(0, |locked_index| (locked_index + 1) % hostile_actors.len())
+        } else {
+            locked_index.unwrap_or(0)
+        };
+        self._locked_target_actor_handle = Some(hostile_actors[target_index].1);
+    }
+
+    // none when the position is behind the camera
+    pub fn convert_world_to_screen(main_camera: &CameraObjectData, window_size: &Vector2<i32>, world_position: &Vector3<f32>) -> Option<Vector2<f32>> {
+        let clip_position = &main_camera._view_projection * Vector4::new(world_position.x, world_position.y, world_position.z, 1.0);
+        if clip_position.w <= 0.0 {
+            return None;
+        }
+        Some(Vector2::new(
+            (clip_position.x / clip_position.w * 0.5 + 0.5) * window_size.x as f32,
+            (clip_position.y / clip_position.w * 0.5 + 0.5) * window_size.y as f32
+        ))
+    }
+
+    // screen rectangle which covers every corner of the bounding box
+    pub fn convert_bound_box_to_screen(main_camera: &CameraObjectData, window_size: &Vector2<i32>, bound_box: &BoundingBox) -> Option<(Vector2<f32>, Vector2<f32>)> {
+        let mut screen_min = Vector2::new(f32::MAX, f32::MAX);
+        let mut screen_max = Vector2::new(f32::MIN, f32::MIN);
+        for corner_index in 0..8 {
+            let corner = Vector3::new(
+                if 0 == (corner_index & 1) { bound_box._min.x } else { bound_box._max.x },
+                if 0 == (corner_index & 2) { bound_box._min.y } else { bound_box._max.y },
+                if 0 == (corner_index & 4) { bound_box._min.z } else { bound_box._max.z }
+            );
+            let screen_pos = GameController::convert_world_to_screen(main_camera, window_size, &corner)?;
+            screen_min = screen_min.inf(&screen_pos);
+            screen_max = screen_max.sup(&screen_pos);
+        }
+        Some((screen_min, screen_max))
+    }
+
+    pub fn get_selected_actor_handles(&self) -> &Vec<ActorHandle> { &self._selected_actor_handles }
+
+    // a click without the drag selects the actor under the mouse
+    pub fn select_actors_in_screen_rect(&mut self, main_camera: &CameraObjectData, window_size: &Vector2<i32>, rect_min: &Vector2<f32>, rect_max: &Vector2<f32>) {
+        let actor_manager = ptr_as_ref(self._game_client).get_actor_manager();
+        let player_faction_id = actor_manager.get_faction_table_data()._player_faction_id;
+        self._selected_actor_handles.clear();
+        for actor in actor_manager.get_actors() {
+            if actor.is_player_actor() || actor.is_dying() || false == actor_manager.is_allied_faction(player_faction_id, actor) {
+                continue;
+            }
+            if let Some((screen_min, screen_max)) = GameController::convert_bound_box_to_screen(main_camera, window_size, actor.get_bound_box()) {
+                if screen_min.x <= rect_max.x && rect_min.x <= screen_max.x && screen_min.y <= rect_max.y && rect_min.y <= screen_max.y {
+                    self._selected_actor_handles.push(actor.get_actor_handle());
+                }
+            }
+        }
+    }
+
+    pub fn issue_ai_command(&mut self, ai_command: &AICommand) {
+        let actor_manager = ptr_as_ref(self._game_client).get_actor_manager();
+        self._selected_actor_handles.retain(|actor_handle| match actor_manager.get_actor(*actor_handle) {
+            Some(actor) => false == actor.is_dying(),
+            None => false,
+        });
+        for actor_handle in self._selected_actor_handles.iter() {
+            if let Some(actor) = actor_manager.get_actor(*actor_handle) {
+                ptr_as_mut(actor).get_ai_controller_mut().set_ai_command(ai_command.clone());
+            }
+        }
+    }
+
+    // the right button attacks the hostile actor under the mouse or moves to the target position, H holds the position
+    pub fn update_selection_event(
+        &mut self,
+        keyboard_input_data: &KeyboardInputData,
+        mouse_input_data: &MouseInputData,
+        main_camera: &CameraObjectData,
+        window_size: &Vector2<i32>
+    ) {
+        if let Some((rect_min, rect_max)) = self.get_game_ui_manager_mut().take_selection_rect() {
+            self.select_actors_in_screen_rect(main_camera, window_size, &rect_min, &rect_max);
+        }
+
+        if self._selected_actor_handles.is_empty() {
+            return;
+        }
+
+        if mouse_input_data._btn_r_pressed {
+            let actor_manager = ptr_as_ref(self._game_client).get_actor_manager();
+            let faction_table_data = actor_manager.get_faction_table_data();
+            let hostile_actor_handle = self._target_actor_handle.filter(|actor_handle| match actor_manager.get_actor(*actor_handle) {
+                Some(actor) => faction_table_data.is_hostile(faction_table_data._player_faction_id, actor.get_faction_id()),
+                None => false,
+            });
+            let ai_command = match hostile_actor_handle {
+                Some(actor_handle) => AICommand::Attack(actor_handle),
+                None => AICommand::Move(self._target_position.clone_owned()),
+            };
+            self.issue_ai_command(&ai_command);
+        } else if keyboard_input_data.get_key_pressed(VirtualKeyCode::H) {
+            self.issue_ai_command(&AICommand::Hold);
+        }
+    }
+
+    // the mouse at the edge of the window scrolls the top view, and the middle button drags it
+    pub fn update_camera_edge_scroll(
+        &mut self,
+        window_size: &Vector2<i32>,
+        mouse_move_data: &MouseMoveData,
+        mouse_input_data: &MouseInputData,
+        mouse_delta: &Vector2<f32>,
+        delta_time: f32
+    ) {
+        let mouse_pos = &mouse_move_data._mouse_pos;
+        let mut scroll_direction: Vector2<f32> = Vector2::zeros();
+        if mouse_pos.x < CAMERA_EDGE_SCROLL_MARGIN {
+            scroll_direction.x = -1.0;
+        } else if (window_size.x - CAMERA_EDGE_SCROLL_MARGIN) < mouse_pos.x {
+            scroll_direction.x = 1.0;
+        }
+        if mouse_pos.y < CAMERA_EDGE_SCROLL_MARGIN {
+            scroll_direction.y = -1.0;
+        } else if (window_size.y - CAMERA_EDGE_SCROLL_MARGIN) < mouse_pos.y {
+            scroll_direction.y = 1.0;
+        }
+
+        // the screen right is the world x and the screen down is the world z
+        let scroll_speed = CAMERA_EDGE_SCROLL_SPEED * delta_time;
+        self._camera_pan_offset.x += scroll_direction.x * scroll_speed;
+        self._camera_pan_offset.z += scroll_direction.y * scroll_speed;
+        if mouse_input_data._btn_m_hold {
+            let drag_speed = self._camera_distance * CAMERA_EDGE_SCROLL_SPEED_BY_MOUSE;
+            self._camera_pan_offset.x -= mouse_delta.x * drag_speed;
+            self._camera_pan_offset.z -= mouse_delta.y * drag_speed;
+        }
+    }
+
+    pub fn update_weapon_group_event(
+        &mut self,
+        joystick_input_data: &JoystickInputData,
+        keyboard_input_data: &KeyboardInputData,
+        player_actor: &mut ActorController,
+        use_bumpers: bool
+    ) {
+        let player_ship = player_actor.get_ship_mut();
+        for (weapon_group, key) in WEAPON_GROUP_KEYS.iter().enumerate() {
+            if keyboard_input_data.get_key_pressed(*key) {
+                player_ship.set_current_weapon_group(weapon_group);
+            }
+        }
+
+        // the bumpers move the ship vertically in the side view
+        if false == use_bumpers {
+            return;
+        }
+
+        if ButtonState::Pressed == joystick_input_data._btn_left_bumper {
+            player_ship.prev_weapon_group();
+        }
+        else if ButtonState::Pressed == joystick_input_data._btn_right_bumper {
+            player_ship.next_weapon_group();
+        }
     }
 
     pub fn update_event_for_side_view_mode(
@@ -109,14 +426,16 @@ impl GameController {
         _main_camera: &mut CameraObjectData,
         player_actor: &mut ActorController
     ) {
-        let btn_left: bool = mouse_input_data._btn_l_pressed;
+        let btn_left: bool = mouse_input_data._btn_l_hold;
         let hold_key_a = keyboard_input_data.get_key_hold(VirtualKeyCode::A);
         let hold_key_d = keyboard_input_data.get_key_hold(VirtualKeyCode::D);
         let hold_key_w = keyboard_input_data.get_key_hold(VirtualKeyCode::W);
         let hold_key_s = keyboard_input_data.get_key_hold(VirtualKeyCode::S);
         let modifier_keys_shift = keyboard_input_data.get_key_hold(VirtualKeyCode::LShift);
 
-        if btn_left || ButtonState::Pressed == joystick_input_data._btn_a {
+        self.update_weapon_group_event(joystick_input_data, keyboard_input_data, player_actor, false);
+
+        if btn_left || ButtonState::Pressed == joystick_input_data._btn_a || ButtonState::Hold == joystick_input_data._btn_a {
             player_actor.set_command_actor_fire();
         }
 
@@ -141,22 +460,150 @@ impl GameController {
         }
     }
 
+    pub fn update_event_for_top_view_mode(
+        &mut self,
+        joystick_input_data: &JoystickInputData,
+        keyboard_input_data: &KeyboardInputData,
+        player_actor: &mut ActorController
+    ) {
+        // the left button drags the selection area, so the weapons are fired by the space key
+        let hold_key_space = keyboard_input_data.get_key_hold(VirtualKeyCode::Space);
+        let hold_key_a = keyboard_input_data.get_key_hold(VirtualKeyCode::A);
+        let hold_key_d = keyboard_input_data.get_key_hold(VirtualKeyCode::D);
+        let hold_key_w = keyboard_input_data.get_key_hold(VirtualKeyCode::W);
+        let hold_key_s = keyboard_input_data.get_key_hold(VirtualKeyCode::S);
+        let modifier_keys_shift = keyboard_input_data.get_key_hold(VirtualKeyCode::LShift);
+
+        self.update_weapon_group_event(joystick_input_data, keyboard_input_data, player_actor, true);
+
+        // the ship faces the target position under the mouse
+        let to_target = &self._relative_target_position;
+        if 0.0 < to_target.x.abs() + to_target.z.abs() {
+            // the forward of the ship is the negative front
+            player_actor.get_controller_mut().set_yaw((-to_target.x).atan2(-to_target.z));
+        }
+
+        if hold_key_space || ButtonState::Pressed == joystick_input_data._btn_a || ButtonState::Hold == joystick_input_data._btn_a {
+            player_actor.set_command_actor_fire();
+        }
+
+        if modifier_keys_shift {
+            player_actor.get_ship_mut().get_controller_mut().boost_on();
+        }
+
+        if hold_key_w || joystick_input_data._btn_up == ButtonState::Hold || joystick_input_data._stick_left_direction.y < 0 {
+            player_actor.set_command_actor_walk();
+        }
+        else if hold_key_s || joystick_input_data._btn_down == ButtonState::Hold || 0 < joystick_input_data._stick_left_direction.y {
+            player_actor.get_ship_mut().get_controller_mut().acceleration_forward(-1.0);
+        }
+
+        if hold_key_a || joystick_input_data._btn_left == ButtonState::Hold || joystick_input_data._stick_left_direction.x < 0 {
+            player_actor.get_ship_mut().get_controller_mut().acceleration_side(-1.0);
+        }
+        else if hold_key_d || joystick_input_data._btn_right == ButtonState::Hold || 0 < joystick_input_data._stick_left_direction.x {
+            player_actor.get_ship_mut().get_controller_mut().acceleration_side(1.0);
+        }
+    }
+
+    pub fn update_event_for_chase_view_mode(
+        &mut self,
+        joystick_input_data: &JoystickInputData,
+        keyboard_input_data: &KeyboardInputData,
+        mouse_input_data: &MouseInputData,
+        mouse_delta: &Vector2<f32>,
+        player_actor: &mut ActorController
+    ) {
+        let btn_left: bool = mouse_input_data._btn_l_hold;
+        let hold_key_a = keyboard_input_data.get_key_hold(VirtualKeyCode::A);
+        let hold_key_d = keyboard_input_data.get_key_hold(VirtualKeyCode::D);
+        let hold_key_w = keyboard_input_data.get_key_hold(VirtualKeyCode::W);
+        let hold_key_s = keyboard_input_data.get_key_hold(VirtualKeyCode::S);
+        let modifier_keys_shift = keyboard_input_data.get_key_hold(VirtualKeyCode::LShift);
+
+        self.update_weapon_group_event(joystick_input_data, keyboard_input_data, player_actor, true);
+
+        // the mouse turns the ship and the camera unless the crosshair is free
+        let is_free_aim = mouse_input_data._btn_r_hold;
+        if false == is_free_aim {
+            let ship_controller = player_actor.get_controller_mut();
+            ship_controller.set_yaw(ship_controller.get_yaw() - mouse_delta.x * CHASE_CAMERA_ROTATION_SPEED);
+            self._chase_camera_pitch = FIRE_PITCH_MIN.max(FIRE_PITCH_MAX.min(self._chase_camera_pitch - mouse_delta.y * CHASE_CAMERA_ROTATION_SPEED));
+        }
+
+        if btn_left || ButtonState::Pressed == joystick_input_data._btn_a || ButtonState::Hold == joystick_input_data._btn_a {
+            player_actor.set_command_actor_fire();
+        }
+
+        if modifier_keys_shift {
+            player_actor.get_ship_mut().get_controller_mut().boost_on();
+        }
+
+        if hold_key_w || joystick_input_data._btn_up == ButtonState::Hold || joystick_input_data._stick_left_direction.y < 0 {
+            player_actor.set_command_actor_walk();
+        }
+        else if hold_key_s || joystick_input_data._btn_down == ButtonState::Hold || 0 < joystick_input_data._stick_left_direction.y {
+            player_actor.get_ship_mut().get_controller_mut().acceleration_forward(-1.0);
+        }
+
+        if hold_key_a || joystick_input_data._btn_left == ButtonState::Hold || joystick_input_data._stick_left_direction.x < 0 {
+            player_actor.get_ship_mut().get_controller_mut().acceleration_side(-1.0);
+        }
+        else if hold_key_d || joystick_input_data._btn_right == ButtonState::Hold || 0 < joystick_input_data._stick_left_direction.x {
+            player_actor.get_ship_mut().get_controller_mut().acceleration_side(1.0);
+        }
+    }
+
     pub fn update_camera(&mut self, delta_time: f32) {
         if self._camera_goal_distance != self._camera_distance {
             self._camera_distance = math::lerp(self._camera_distance, self._camera_goal_distance, 1.0f32.min(delta_time * CAMERA_DISTANCE_SPEED));
         }
 
-        let player_actor = self.get_game_client().get_actor_manager().get_player_actor();
+        let actor_manager = self.get_game_client().get_actor_manager();
+        // the player actor may be removed already while the death camera is running
+        let player_position = match actor_manager.get_player_position() {
+            Some(player_position) => player_position,
+            None => return,
+        };
         let main_camera = self.get_main_camera_mut();
-        let player_transform = player_actor.get_transform();
+
+        // the death camera pulls away from the wreck until the respawn
+        let mut camera_distance = self._camera_distance;
+        let mut death_camera_height: f32 = 0.0;
+        if actor_manager.is_player_dead() {
+            let player_dead_time = actor_manager.get_player_dead_time();
+            camera_distance += player_dead_time * DEATH_CAMERA_DISTANCE_SPEED;
+            death_camera_height = player_dead_time * DEATH_CAMERA_HEIGHT_SPEED;
+        }
 
         if GameViewMode::SideViewMode == self._game_view_mode {
             main_camera._transform_object.set_yaw(std::f32::consts::PI);
-            let mut camera_pos = player_transform.get_position().clone_owned();
-            camera_pos.y += CAMERA_VERTICAL_OFFSET;
-            camera_pos.z -= self._camera_distance;
+            let mut camera_pos = player_position.clone_owned();
+            camera_pos.y += CAMERA_VERTICAL_OFFSET + death_camera_height;
+            camera_pos.z -= camera_distance;
             main_camera._transform_object.set_position(&camera_pos);
             // main_camera._transform_object.update_transform_object();
+        } else if GameViewMode::TopViewMode == self._game_view_mode {
+            main_camera._transform_object.set_rotation(&Vector3::new(TOP_VIEW_CAMERA_PITCH, 0.0, 0.0));
+            let mut camera_pos = player_position + &self._camera_pan_offset;
+            camera_pos.y += camera_distance + CAMERA_VERTICAL_OFFSET + death_camera_height;
+            main_camera._transform_object.set_position(&camera_pos);
+        } else if GameViewMode::ChaseViewMode == self._game_view_mode {
+            // keeps the last yaw while the player is dead
+            if let Some(player_actor) = actor_manager.get_player_actor() {
+                self._chase_camera_yaw = player_actor.get_controller().get_yaw();
+            }
+
+            // the forward of the ship is the negative front, the camera looks at the same direction
+            let yaw = self._chase_camera_yaw;
+            let pitch = self._chase_camera_pitch;
+            let forward = Vector3::new(-yaw.sin() * pitch.cos(), pitch.sin(), -yaw.cos() * pitch.cos());
+            let mut goal_camera_pos = player_position - forward * camera_distance * CHASE_CAMERA_DISTANCE_RATIO;
+            goal_camera_pos.y += CHASE_CAMERA_VERTICAL_OFFSET + death_camera_height;
+            let spring_ratio = 1.0f32.min(delta_time * CHASE_CAMERA_SPRING_SPEED);
+            self._chase_camera_position += (goal_camera_pos - &self._chase_camera_position) * spring_ratio;
+            main_camera._transform_object.set_rotation(&Vector3::new(pitch, yaw, 0.0));
+            main_camera._transform_object.set_position(&self._chase_camera_position);
         } else {
             assert!(false, "Not implemented.");
         }
@@ -0,0 +1,79 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
+use crate::game_module::actor_manager::ActorHandle;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::ship::ship::ShipDamageResult;
+
+#[derive(Clone, Debug)]
+pub enum GameEvent {
+    ActorSpawned {
+        _actor_handle: ActorHandle,
+        _is_player_actor: bool,
+    },
+    ActorDamaged {
+        _actor_handle: ActorHandle,
+        _attacker_actor_handle: ActorHandle,
+        _damage_result: ShipDamageResult,
+    },
+    ActorDestroyed {
+        _actor_handle: ActorHandle,
+        _attacker_actor_handle: ActorHandle,
+        _position: Vector3<f32>,
+    },
+    WeaponFired {
+        _owner_actor_handle: ActorHandle,
+        _position: Vector3<f32>,
+    },
+    ObjectiveCompleted {
+        _objective_index: usize,
+    },
+}
+
+// The events published during a frame are dispatched at the beginning of the next frame,
+// so every system reads the same events once regardless of the update order.
+pub struct GameEventManager {
+    pub _game_client: *const GameClient,
+    pub _published_events: Vec<GameEvent>,
+    pub _dispatched_events: Vec<GameEvent>,
+}
+
+impl GameEventManager {
+    pub fn create_game_event_manager() -> Box<GameEventManager> {
+        Box::new(GameEventManager {
+            _game_client: std::ptr::null(),
+            _published_events: Vec::new(),
+            _dispatched_events: Vec::new(),
+        })
+    }
+
+    pub fn initialize_game_event_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+
+    pub fn destroy_game_event_manager(&mut self) {
+        self.clear_events();
+    }
+
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
+
+    pub fn publish_event(&mut self, game_event: GameEvent) {
+        self._published_events.push(game_event);
+    }
+
+    // events of the previous frame
+    pub fn get_events(&self) -> &Vec<GameEvent> {
+        &self._dispatched_events
+    }
+
+    pub fn clear_events(&mut self) {
+        self._published_events.clear();
+        self._dispatched_events.clear();
+    }
+
+    pub fn update_game_event_manager(&mut self) {
+        self._dispatched_events.clear();
+        std::mem::swap(&mut self._published_events, &mut self._dispatched_events);
+    }
+}
@@ -0,0 +1,27 @@
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_states::game_state::{GameStateTrait, GameStateType, GameStateTransition};
+
+// first state of the game client, nothing is loaded yet
+pub struct BootState {
+}
+
+impl BootState {
+    pub fn create_boot_state() -> Box<BootState> {
+        Box::new(BootState {})
+    }
+}
+
+impl GameStateTrait for BootState {
+    fn get_game_state_type(&self) -> GameStateType { GameStateType::Boot }
+    fn enter_state(&mut self, game_client: &GameClient) {
+        let game_ui_manager = game_client.get_game_ui_manager_mut();
+        game_ui_manager.show_game_hud(false);
+        game_ui_manager.hide_message();
+    }
+    fn update_event_state(&mut self, _game_client: &GameClient) -> GameStateTransition {
+        GameStateTransition::None
+    }
+    fn update_state(&mut self, _game_client: &GameClient, _delta_time: f32) -> GameStateTransition {
+        GameStateTransition::Change(GameStateType::MainMenu)
+    }
+}
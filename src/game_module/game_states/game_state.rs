@@ -0,0 +1,161 @@
+use winit::event::VirtualKeyCode;
+
+use rust_engine_3d::application::input::ButtonState;
+use rust_engine_3d::utilities::system::ptr_as_ref;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_states::boot_state::BootState;
+use crate::game_module::game_states::in_mission_state::InMissionState;
+use crate::game_module::game_states::loading_state::LoadingState;
+use crate::game_module::game_states::main_menu_state::MainMenuState;
+use crate::game_module::game_states::mission_complete_state::MissionCompleteState;
+use crate::game_module::game_states::mission_failed_state::MissionFailedState;
+use crate::game_module::game_states::paused_state::PausedState;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameStateType {
+    Boot,
+    MainMenu,
+    Loading,
+    InMission,
+    Paused,
+    MissionFailed,
+    MissionComplete,
+}
+
+// requested by the hooks of the top state and applied after the hook returns
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameStateTransition {
+    None,
+    Push(GameStateType),
+    Pop,
+    Change(GameStateType),
+    // exits every state of the stack, then pushes the state
+    Reset(GameStateType),
+}
+
+pub trait GameStateTrait {
+    fn get_game_state_type(&self) -> GameStateType;
+    fn enter_state(&mut self, _game_client: &GameClient) {}
+    fn exit_state(&mut self, _game_client: &GameClient) {}
+    fn update_event_state(&mut self, game_client: &GameClient) -> GameStateTransition;
+    fn update_state(&mut self, game_client: &GameClient, delta_time: f32) -> GameStateTransition;
+}
+
+pub fn create_game_state(game_state_type: GameStateType) -> Box<dyn GameStateTrait> {
+    match game_state_type {
+        GameStateType::Boot => BootState::create_boot_state(),
+        GameStateType::MainMenu => MainMenuState::create_main_menu_state(),
+        GameStateType::Loading => LoadingState::create_loading_state(),
+        GameStateType::InMission => InMissionState::create_in_mission_state(),
+        GameStateType::Paused => PausedState::create_paused_state(),
+        GameStateType::MissionFailed => MissionFailedState::create_mission_failed_state(),
+        GameStateType::MissionComplete => MissionCompleteState::create_mission_complete_state(),
+    }
+}
+
+pub fn is_confirm_pressed(game_client: &GameClient) -> bool {
+    let engine_application = game_client.get_project_application().get_engine_application();
+    engine_application._keyboard_input_data.get_key_pressed(VirtualKeyCode::Return) ||
+        ButtonState::Pressed == engine_application._joystick_input_data._btn_a
+}
+
+pub fn is_cancel_pressed(game_client: &GameClient) -> bool {
+    let engine_application = game_client.get_project_application().get_engine_application();
+    engine_application._keyboard_input_data.get_key_pressed(VirtualKeyCode::Back) ||
+        ButtonState::Pressed == engine_application._joystick_input_data._btn_b
+}
+
+pub fn is_pause_pressed(game_client: &GameClient) -> bool {
+    let engine_application = game_client.get_project_application().get_engine_application();
+    engine_application._keyboard_input_data.get_key_pressed(VirtualKeyCode::P)
+}
+
+// Only the top state of the stack receives the events and the updates,
+// the states below of it such as the mission under the pause menu are frozen.
+pub struct GameStateManager {
+    pub _game_client: *const GameClient,
+    pub _game_states: Vec<Box<dyn GameStateTrait>>,
+}
+
+impl GameStateManager {
+    pub fn create_game_state_manager() -> Box<GameStateManager> {
+        Box::new(GameStateManager {
+            _game_client: std::ptr::null(),
+            _game_states: Vec::new(),
+        })
+    }
+
+    pub fn initialize_game_state_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+
+    pub fn destroy_game_state_manager(&mut self) {
+        self.clear_states();
+    }
+
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+
+    pub fn get_current_game_state_type(&self) -> Option<GameStateType> {
+        self._game_states.last().map(|game_state| game_state.get_game_state_type())
+    }
+
+    pub fn push_state(&mut self, game_state_type: GameStateType) {
+        log::info!("push game state: {:?}", game_state_type);
+        let game_client = ptr_as_ref(self._game_client);
+        let mut game_state = create_game_state(game_state_type);
+        game_state.enter_state(game_client);
+        self._game_states.push(game_state);
+    }
+
+    pub fn pop_state(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        if let Some(mut game_state) = self._game_states.pop() {
+            log::info!("pop game state: {:?}", game_state.get_game_state_type());
+            game_state.exit_state(game_client);
+        }
+    }
+
+    pub fn change_state(&mut self, game_state_type: GameStateType) {
+        self.pop_state();
+        self.push_state(game_state_type);
+    }
+
+    pub fn clear_states(&mut self) {
+        while false == self._game_states.is_empty() {
+            self.pop_state();
+        }
+    }
+
+    pub fn reset_state(&mut self, game_state_type: GameStateType) {
+        self.clear_states();
+        self.push_state(game_state_type);
+    }
+
+    fn apply_transition(&mut self, transition: GameStateTransition) {
+        match transition {
+            GameStateTransition::None => (),
+            GameStateTransition::Push(game_state_type) => self.push_state(game_state_type),
+            GameStateTransition::Pop => self.pop_state(),
+            GameStateTransition::Change(game_state_type) => self.change_state(game_state_type),
+            GameStateTransition::Reset(game_state_type) => self.reset_state(game_state_type),
+        }
+    }
+
+    pub fn update_event(&mut self) {
+        let game_client = ptr_as_ref(self._game_client);
+        let transition = match self._game_states.last_mut() {
+            Some(game_state) => game_state.update_event_state(game_client),
+            None => GameStateTransition::None,
+        };
+        self.apply_transition(transition);
+    }
+
+    pub fn update_game_state_manager(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        let transition = match self._game_states.last_mut() {
+            Some(game_state) => game_state.update_state(game_client, delta_time),
+            None => GameStateTransition::None,
+        };
+        self.apply_transition(transition);
+    }
+}
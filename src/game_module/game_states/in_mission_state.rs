@@ -0,0 +1,40 @@
+use crate::game_module::game_client::GameClient;
+use crate::game_module::objective_manager::MissionResult;
+use crate::game_module::game_states::game_state::{GameStateTrait, GameStateType, GameStateTransition, is_pause_pressed};
+
+// The mission stays loaded while the pause or the result states are pushed over it,
+// and is closed when this state exits.
+pub struct InMissionState {
+}
+
+impl InMissionState {
+    pub fn create_in_mission_state() -> Box<InMissionState> {
+        Box::new(InMissionState {})
+    }
+}
+
+impl GameStateTrait for InMissionState {
+    fn get_game_state_type(&self) -> GameStateType { GameStateType::InMission }
+    fn enter_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().show_game_hud(true);
+    }
+    fn exit_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().show_game_hud(false);
+        game_client.end_mission();
+    }
+    fn update_event_state(&mut self, game_client: &GameClient) -> GameStateTransition {
+        if is_pause_pressed(game_client) {
+            return GameStateTransition::Push(GameStateType::Paused);
+        }
+        game_client.update_mission_event();
+        GameStateTransition::None
+    }
+    fn update_state(&mut self, game_client: &GameClient, delta_time: f32) -> GameStateTransition {
+        game_client.update_mission(delta_time);
+        match game_client.get_objective_manager().get_mission_result() {
+            MissionResult::InProgress => GameStateTransition::None,
+            MissionResult::Complete => GameStateTransition::Push(GameStateType::MissionComplete),
+            MissionResult::Failed => GameStateTransition::Push(GameStateType::MissionFailed),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::MISSION_SCENE_NAME;
+use crate::game_module::game_states::game_state::{GameStateTrait, GameStateType, GameStateTransition};
+
+// the scene is loaded synchronously on enter, so the loading message is shown for a frame
+pub struct LoadingState {
+}
+
+impl LoadingState {
+    pub fn create_loading_state() -> Box<LoadingState> {
+        Box::new(LoadingState {})
+    }
+}
+
+impl GameStateTrait for LoadingState {
+    fn get_game_state_type(&self) -> GameStateType { GameStateType::Loading }
+    fn enter_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().show_message("Loading...");
+        game_client.start_mission(MISSION_SCENE_NAME);
+    }
+    fn exit_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().hide_message();
+    }
+    fn update_event_state(&mut self, _game_client: &GameClient) -> GameStateTransition {
+        GameStateTransition::None
+    }
+    fn update_state(&mut self, _game_client: &GameClient, _delta_time: f32) -> GameStateTransition {
+        GameStateTransition::Change(GameStateType::InMission)
+    }
+}
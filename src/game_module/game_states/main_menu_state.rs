@@ -0,0 +1,30 @@
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_states::game_state::{GameStateTrait, GameStateType, GameStateTransition, is_confirm_pressed};
+
+pub struct MainMenuState {
+}
+
+impl MainMenuState {
+    pub fn create_main_menu_state() -> Box<MainMenuState> {
+        Box::new(MainMenuState {})
+    }
+}
+
+impl GameStateTrait for MainMenuState {
+    fn get_game_state_type(&self) -> GameStateType { GameStateType::MainMenu }
+    fn enter_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().show_message("THIRD IMPACT - Press Enter to start");
+    }
+    fn exit_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().hide_message();
+    }
+    fn update_event_state(&mut self, game_client: &GameClient) -> GameStateTransition {
+        if is_confirm_pressed(game_client) {
+            return GameStateTransition::Change(GameStateType::Loading);
+        }
+        GameStateTransition::None
+    }
+    fn update_state(&mut self, _game_client: &GameClient, _delta_time: f32) -> GameStateTransition {
+        GameStateTransition::None
+    }
+}
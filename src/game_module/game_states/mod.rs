@@ -0,0 +1,8 @@
+pub mod boot_state;
+pub mod game_state;
+pub mod in_mission_state;
+pub mod loading_state;
+pub mod main_menu_state;
+pub mod mission_complete_state;
+pub mod mission_failed_state;
+pub mod paused_state;
@@ -0,0 +1,33 @@
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_states::game_state::{GameStateTrait, GameStateType, GameStateTransition, is_cancel_pressed, is_pause_pressed};
+
+pub struct PausedState {
+}
+
+impl PausedState {
+    pub fn create_paused_state() -> Box<PausedState> {
+        Box::new(PausedState {})
+    }
+}
+
+impl GameStateTrait for PausedState {
+    fn get_game_state_type(&self) -> GameStateType { GameStateType::Paused }
+    fn enter_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().show_message("Paused - P: resume, Backspace: main menu");
+    }
+    fn exit_state(&mut self, game_client: &GameClient) {
+        game_client.get_game_ui_manager_mut().hide_message();
+    }
+    fn update_event_state(&mut self, game_client: &GameClient) -> GameStateTransition {
+        if is_pause_pressed(game_client) {
+            GameStateTransition::Pop
+        } else if is_cancel_pressed(game_client) {
+            GameStateTransition::Reset(GameStateType::MainMenu)
+        } else {
+            GameStateTransition::None
+        }
+    }
+    fn update_state(&mut self, _game_client: &GameClient, _delta_time: f32) -> GameStateTransition {
+        GameStateTransition::None
+    }
+}
@@ -3,8 +3,12 @@ use nalgebra::{ Vector2 };
 use rust_engine_3d::application::scene_manager::ProjectSceneManagerBase;
 use rust_engine_3d::renderer::ui::{Widget, WidgetDefault};
 use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
+use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
 use crate::game_module::game_client::GameClient;
-use crate::game_module::ui_widgets::hud::{CrossHair, TargetHud, PlayerHud, SelectionArea};
+use crate::game_module::game_constants::{OBJECTIVE_MESSAGE_TIME, PLAYER_HIT_FLASH_TIME};
+use crate::game_module::game_controller::GameController;
+use crate::game_module::game_event_manager::GameEvent;
+use crate::game_module::ui_widgets::hud::{CrossHair, TargetHud, PlayerHud, SelectionArea, MessageBox, ObjectiveHud};
 use crate::renderer::project_ui::ProjectUIManager;
 
 pub struct GameUIManager {
@@ -13,7 +17,13 @@ pub struct GameUIManager {
     pub _crosshair: Option<CrossHair>,
     pub _target_hud: Option<TargetHud>,
     pub _player_hud: Option<PlayerHud>,
-    pub _selection_area: Option<Box<SelectionArea>>
+    pub _objective_hud: Option<ObjectiveHud>,
+    pub _selection_area: Option<Box<SelectionArea>>,
+    pub _message_box: Option<MessageBox>,
+    // the message is hidden when the time runs out, zero keeps the message
+    pub _message_time: f32,
+    // the border of the player hud flashes while the time remains
+    pub _player_hit_flash_time: f32,
 }
 
 impl GameUIManager {
@@ -24,7 +34,11 @@ impl GameUIManager {
             _crosshair: None,
             _target_hud: None,
             _player_hud: None,
+            _objective_hud: None,
             _selection_area: None,
+            _message_box: None,
+            _message_time: 0.0,
+            _player_hit_flash_time: 0.0,
         })
     }
 
@@ -44,7 +58,9 @@ impl GameUIManager {
         self._crosshair = Some(CrossHair::create_crosshair(project_resources, game_ui_layout_mut, &window_center));
         self._target_hud = Some(TargetHud::create_target_hud(game_ui_layout_mut, &window_center));
         self._player_hud = Some(PlayerHud::create_player_hud(game_ui_layout_mut, &Vector2::new(window_size.x as f32 - 200.0, window_center.y as f32)));
+        self._objective_hud = Some(ObjectiveHud::create_objective_hud(game_ui_layout_mut, &Vector2::new(20.0, 20.0)));
         self._selection_area = Some(SelectionArea::create_selection_area(game_ui_layout_mut, window_size));
+        self._message_box = Some(MessageBox::create_message_box(game_ui_layout_mut, &window_center));
     }
 
     pub fn destroy_game_ui_manager(&mut self) {
@@ -60,11 +76,55 @@ impl GameUIManager {
         ui_component.set_visible(show);
     }
 
+    pub fn take_selection_rect(&mut self) -> Option<(Vector2<f32>, Vector2<f32>)> {
+        self._selection_area.as_mut().unwrap()._selection_rect.take()
+    }
+
     pub fn show_crosshair(&mut self, show: bool) {
         let ui_component = self.get_crosshair_widget_mut().get_ui_component_mut();
         ui_component.set_visible(show);
     }
 
+    // hud of the mission, hidden in the menus
+    pub fn show_game_hud(&mut self, show: bool) {
+        self.show_crosshair(show);
+        ptr_as_mut(self._target_hud.as_ref().unwrap()._widget).get_ui_component_mut().set_visible(show);
+        ptr_as_mut(self._player_hud.as_ref().unwrap()._widget).get_ui_component_mut().set_visible(show);
+        ptr_as_mut(self._objective_hud.as_ref().unwrap()._widget).get_ui_component_mut().set_visible(show);
+    }
+
+    pub fn show_message(&mut self, message: &str) {
+        self._message_box.as_ref().unwrap().show_message(message);
+        self._message_time = 0.0;
+    }
+
+    pub fn show_timed_message(&mut self, message: &str, message_time: f32) {
+        self._message_box.as_ref().unwrap().show_message(message);
+        self._message_time = message_time;
+    }
+
+    pub fn hide_message(&mut self) {
+        self._message_box.as_ref().unwrap().hide_message();
+        self._message_time = 0.0;
+    }
+
+    pub fn process_game_events(&mut self, game_client: &GameClient) {
+        let player_actor_handle = game_client.get_actor_manager()._player_actor_handle;
+        for game_event in game_client.get_game_event_manager().get_events().iter() {
+            match game_event {
+                GameEvent::ObjectiveCompleted { _objective_index } => {
+                    if let Some(objective) = game_client.get_objective_manager().get_objectives().get(*_objective_index) {
+                        self.show_timed_message(&format!("Objective complete: {}", objective.get_description()), OBJECTIVE_MESSAGE_TIME);
+                    }
+                },
+                GameEvent::ActorDamaged { _actor_handle, .. } if Some(*_actor_handle) == player_actor_handle => {
+                    self._player_hit_flash_time = PLAYER_HIT_FLASH_TIME;
+                },
+                _ => (),
+            }
+        }
+    }
+
     pub fn set_crosshair_tracking_mouse(&mut self, tracking: bool) {
         self._crosshair.as_mut().unwrap()._tracking_mouse = tracking;
     }
@@ -73,27 +133,73 @@ impl GameUIManager {
         self._crosshair.as_mut().unwrap()._pos.clone_from(pos);
     }
 
-    pub fn update_game_ui(&mut self, _delta_time: f32) {
+    // the crosshair stays at the center of the screen unless it tracks the mouse
+    pub fn get_crosshair_screen_pos(&self) -> Vector2<i32> {
+        let crosshair = self._crosshair.as_ref().unwrap();
+        if crosshair._tracking_mouse {
+            crosshair._pos.clone_owned()
+        } else {
+            let window_size = &self.get_game_client().get_project_application().get_engine_application()._window_size;
+            Vector2::new(window_size.x / 2, window_size.y / 2)
+        }
+    }
+
+    pub fn update_game_ui(&mut self, delta_time: f32) {
         let game_client = ptr_as_ref(self._game_client);
-        let _main_camera = game_client.get_project_scene_manager().get_main_camera();
+        self.process_game_events(game_client);
+        self._player_hit_flash_time = 0f32.max(self._player_hit_flash_time - delta_time);
+        if 0.0 < self._message_time {
+            self._message_time -= delta_time;
+            if self._message_time <= 0.0 {
+                self.hide_message();
+            }
+        }
+
+        let main_camera = game_client.get_project_scene_manager().get_main_camera();
         let window_size = &game_client.get_project_application().get_engine_application()._window_size;
 
         // Cross Hair
-        let crosshair = self._crosshair.as_ref().unwrap();
-        let crosshair_widget = ptr_as_mut(crosshair._widget);
+        let crosshair_widget = ptr_as_mut(self._crosshair.as_ref().unwrap()._widget);
         if crosshair_widget.get_ui_component().get_visible() {
-            let crosshair_pos_x: i32;
-            let crosshair_pos_y: i32;
-
-            if crosshair._tracking_mouse {
-                crosshair_pos_x = crosshair._pos.x;
-                crosshair_pos_y = crosshair._pos.y;
-            } else {
-                crosshair_pos_x = window_size.x / 2;
-                crosshair_pos_y = window_size.y / 2;
-            }
+            let crosshair_pos = self.get_crosshair_screen_pos();
             let ui_component = crosshair_widget.get_ui_component_mut();
-            ui_component.set_center(crosshair_pos_x as f32, crosshair_pos_y as f32);
+            ui_component.set_center(crosshair_pos.x as f32, crosshair_pos.y as f32);
+        }
+
+        // Player Hud
+        let actor_manager = game_client.get_actor_manager();
+        let player_hud = self._player_hud.as_ref().unwrap();
+        // the respawn countdown is shown while there is no player actor
+        player_hud.update_lives_widget(actor_manager.get_player_lives(), actor_manager.is_player_dead(), actor_manager.get_player_respawn_time());
+        if let Some(player_actor) = actor_manager.get_player_actor() {
+            let player_ship = player_actor.get_ship();
+            let border_color = if 0.0 < self._player_hit_flash_time { get_color32(255, 0, 0, 255) } else { actor_manager.get_faction_table_data().get_hud_color(player_actor.get_faction_id()) };
+            player_hud.update_faction_color(border_color);
+            player_hud.update_weapon_group_widget(player_ship.get_current_weapon_group(), player_ship.get_weapon_group_count());
+            player_hud._hull_point_widget.update_hull_point_widget(player_ship.get_hull_point(), player_ship.get_max_hull_point());
+            player_hud._shield_point_widget.update_shield_point_widget(player_ship.get_shield_point(), player_ship.get_max_shield_point());
         }
+
+        // Target Hud
+        let target_hud = self._target_hud.as_ref().unwrap();
+        let locked_target_actor = game_client.get_game_controller().get_locked_target_actor_handle().and_then(|actor_handle| actor_manager.get_actor(actor_handle));
+        let mut show_target_hud = false;
+        if let (Some(player_actor), Some(target_actor)) = (actor_manager.get_player_actor(), locked_target_actor) {
+            // the target behind the camera has no place on the screen
+            if let Some((screen_min, screen_max)) = GameController::convert_bound_box_to_screen(main_camera, window_size, target_actor.get_bound_box()) {
+                let distance = (target_actor.get_transform().get_position() - player_actor.get_transform().get_position()).norm();
+                let relation_color = actor_manager.get_faction_table_data().get_relation_color(player_actor.get_faction_id(), target_actor.get_faction_id());
+                let target_ship = target_actor.get_ship();
+                target_hud.update_target_hud(&screen_min, &screen_max, distance, relation_color);
+                target_hud._hull_point_widget.update_hull_point_widget(target_ship.get_hull_point(), target_ship.get_max_hull_point());
+                target_hud._shield_point_widget.update_shield_point_widget(target_ship.get_shield_point(), target_ship.get_max_shield_point());
+                show_target_hud = true;
+            }
+        }
+        target_hud.show_target_hud(show_target_hud);
+
+        // Objective Hud
+        let objective_manager = game_client.get_objective_manager();
+        self._objective_hud.as_ref().unwrap().update_objective_hud(objective_manager.get_objectives());
     }
 }
\ No newline at end of file
@@ -1,11 +1,31 @@
 use serde::{ Serialize, Deserialize };
 
-use crate::game_module::level_datas::spawn_point::{ SpawnPointType, ShipSpawnPointData };
+use crate::game_module::game_constants::DEFAULT_PLAYER_LIVES;
+use crate::game_module::level_datas::objective::{ ObjectiveData, ObjectiveType };
+use crate::game_module::level_datas::spawn_point::{ SpawnPointType, ShipSpawnPointData, WaveData };
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(default)]
 pub struct LevelData {
+    pub _faction_table_name: String,
     pub _spawn_point_datas: Vec<SpawnPointType>,
+    pub _waves: Vec<WaveData>,
+    // the mission is complete when every objective is done, destroy all enemies if empty
+    pub _objectives: Vec<ObjectiveData>,
+    // the player respawns at the player spawn point until the lives run out
+    pub _player_lives: u32,
+}
+
+impl Default for LevelData {
+    fn default() -> LevelData {
+        LevelData {
+            _faction_table_name: String::new(),
+            _spawn_point_datas: Vec::new(),
+            _waves: Vec::new(),
+            _objectives: Vec::new(),
+            _player_lives: DEFAULT_PLAYER_LIVES,
+        }
+    }
 }
 
 impl LevelData {
@@ -21,6 +41,12 @@ impl LevelData {
                     ..Default::default()
                 })
             ],
+            _objectives: vec![
+                ObjectiveData {
+                    _description: "Destroy all enemies".to_string(),
+                    _objective_type: ObjectiveType::DestroyAllEnemies,
+                }
+            ],
             ..Default::default()
         }
     }
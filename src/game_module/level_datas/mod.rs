@@ -1,2 +1,3 @@
+pub mod objective;
 pub mod spawn_point;
-pub mod level_data;
\ No newline at end of file
+pub mod level_data;
@@ -0,0 +1,32 @@
+use nalgebra::Vector3;
+use serde::{ Serialize, Deserialize };
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ZoneData {
+    pub _position: Vector3<f32>,
+    pub _radius: f32,
+}
+
+// the target names refer to the _actor_name of the ship spawn points
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum ObjectiveType {
+    DestroyAllEnemies,
+    DestroyTarget(String),
+    Survive(f32),
+    ReachZone(ZoneData),
+    ProtectAlly(String),
+}
+
+impl Default for ObjectiveType {
+    fn default() -> ObjectiveType {
+        ObjectiveType::DestroyAllEnemies
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct ObjectiveData {
+    pub _description: String,
+    pub _objective_type: ObjectiveType,
+}
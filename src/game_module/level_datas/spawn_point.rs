@@ -6,12 +6,70 @@ pub enum SpawnPointType {
     None,
     Player(ShipSpawnPointData),
     NonPlayer(ShipSpawnPointData),
+    Spawner(SpawnerData),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(default)]
 pub struct ShipSpawnPointData {
+    pub _actor_name: String,
     pub _ship_data_name: String,
+    pub _ai_profile_name: String,
+    pub _faction_name: String,
     pub _position: Vector3<f32>,
     pub _rotation: Vector3<f32>
+}
+
+// emits the non player ships repeatedly from the spawn point
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct SpawnerData {
+    pub _spawn_point_data: ShipSpawnPointData,
+    pub _delay: f32,
+    pub _interval: f32,
+    // 0 spawns forever
+    pub _count: u32,
+    // 0 is unlimited
+    pub _max_alive_count: u32,
+    pub _spawn_radius: f32,
+}
+
+impl Default for SpawnerData {
+    fn default() -> SpawnerData {
+        SpawnerData {
+            _spawn_point_data: ShipSpawnPointData::default(),
+            _delay: 0.0,
+            _interval: 10.0,
+            _count: 0,
+            _max_alive_count: 4,
+            _spawn_radius: 10.0,
+        }
+    }
+}
+
+// The delay of a wave counts from the mission start,
+// or from the moment the previous wave is cleared if the wave waits for it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WaveData {
+    pub _spawn_point_data: ShipSpawnPointData,
+    pub _delay: f32,
+    // at least 1
+    pub _count: u32,
+    pub _interval: f32,
+    pub _spawn_radius: f32,
+    pub _wait_previous_wave_cleared: bool,
+}
+
+impl Default for WaveData {
+    fn default() -> WaveData {
+        WaveData {
+            _spawn_point_data: ShipSpawnPointData::default(),
+            _delay: 0.0,
+            _count: 1,
+            _interval: 1.0,
+            _spawn_radius: 10.0,
+            _wait_previous_wave_cleared: false,
+        }
+    }
 }
\ No newline at end of file
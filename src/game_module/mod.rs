@@ -1,12 +1,18 @@
+pub mod collision;
+pub mod faction;
 pub mod game_constants;
 pub mod game_controller;
 pub mod game_client;
+pub mod game_event_manager;
 pub mod game_states;
 pub mod level_datas;
+pub mod objective_manager;
 pub mod game_ui;
 pub mod actors;
 pub mod ship;
 pub mod weapons;
 pub mod actor_manager;
 pub mod weapon_manager;
+pub mod spatial_grid;
+pub mod spawn_manager;
 pub mod ui_widgets;
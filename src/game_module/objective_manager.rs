@@ -0,0 +1,247 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
+use crate::game_module::actor_manager::ActorManager;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_event_manager::GameEvent;
+use crate::game_module::level_datas::level_data::LevelData;
+use crate::game_module::level_datas::objective::{ObjectiveData, ObjectiveType};
+use crate::game_module::level_datas::spawn_point::SpawnPointType;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ObjectiveState {
+    InProgress,
+    Completed,
+    Failed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MissionResult {
+    InProgress,
+    Complete,
+    Failed,
+}
+
+pub struct Objective {
+    pub _objective_data: ObjectiveData,
+    pub _objective_state: ObjectiveState,
+    pub _progress_text: String,
+}
+
+pub struct ObjectiveManager {
+    pub _game_client: *const GameClient,
+    pub _objectives: Vec<Objective>,
+    pub _mission_time: f32,
+    pub _mission_result: MissionResult,
+}
+
+impl Objective {
+    pub fn create_objective(objective_data: &ObjectiveData) -> Objective {
+        Objective {
+            _objective_data: objective_data.clone(),
+            _objective_state: ObjectiveState::InProgress,
+            _progress_text: String::new(),
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self._objective_data._description
+    }
+
+    pub fn get_objective_state(&self) -> ObjectiveState {
+        self._objective_state
+    }
+
+    pub fn get_progress_text(&self) -> &str {
+        &self._progress_text
+    }
+
+    // the protect objectives have no end by themselves, they only fail
+    pub fn is_protect_objective(&self) -> bool {
+        match self._objective_data._objective_type {
+            ObjectiveType::ProtectAlly(_) => true,
+            _ => false,
+        }
+    }
+
+    // the protect objectives are satisfied as long as they are not failed
+    pub fn is_satisfied(&self) -> bool {
+        match self._objective_state {
+            ObjectiveState::Completed => true,
+            ObjectiveState::Failed => false,
+            ObjectiveState::InProgress => self.is_protect_objective(),
+        }
+    }
+
+    // the player position is none while the player is dead, the player actor may be removed already
+    pub fn update_objective(&mut self, actor_manager: &ActorManager, player_position: Option<&Vector3<f32>>, has_pending_spawns: bool, mission_time: f32) {
+        if ObjectiveState::InProgress != self._objective_state {
+            return;
+        }
+
+        match &self._objective_data._objective_type {
+            ObjectiveType::DestroyAllEnemies => {
+                // the waves which are not spawned yet also count
+                let enemy_count = actor_manager.get_hostile_actor_count(actor_manager.get_faction_table_data()._player_faction_id);
+                self._progress_text = if has_pending_spawns { format!("{} left, more incoming", enemy_count) } else { format!("{} left", enemy_count) };
+                if 0 == enemy_count && false == has_pending_spawns {
+                    self._objective_state = ObjectiveState::Completed;
+                }
+            },
+            ObjectiveType::DestroyTarget(actor_name) => {
+                // the actor of a wave or a spawner is not spawned yet, which is not destroyed
+                if false == actor_manager.is_actor_name_registered(actor_name) {
+                    self._progress_text = "not arrived".to_string();
+                    return;
+                }
+                let is_destroyed = match actor_manager.get_actor_by_name(actor_name) {
+                    Some(actor) => actor.get_ship().is_destroyed(),
+                    None => true,
+                };
+                self._progress_text = if is_destroyed { "1/1".to_string() } else { "0/1".to_string() };
+                if is_destroyed {
+                    self._objective_state = ObjectiveState::Completed;
+                }
+            },
+            ObjectiveType::Survive(survive_time) => {
+                self._progress_text = format!("{:.0}/{:.0}s", mission_time.min(*survive_time), survive_time);
+                if *survive_time <= mission_time {
+                    self._objective_state = ObjectiveState::Completed;
+                }
+            },
+            ObjectiveType::ReachZone(zone_data) => {
+                let player_position = match player_position {
+                    Some(player_position) => player_position,
+                    None => return,
+                };
+                let distance = (player_position - &zone_data._position).norm();
+                self._progress_text = format!("{:.0}m", 0f32.max(distance - zone_data._radius));
+                if distance <= zone_data._radius {
+                    self._objective_state = ObjectiveState::Completed;
+                }
+            },
+            ObjectiveType::ProtectAlly(actor_name) => {
+                if false == actor_manager.is_actor_name_registered(actor_name) {
+                    self._progress_text = "not arrived".to_string();
+                    return;
+                }
+                let hull_ratio = match actor_manager.get_actor_by_name(actor_name) {
+                    Some(actor) if false == actor.get_ship().is_destroyed() => {
+                        let ship = actor.get_ship();
+                        ship.get_hull_point() / ship.get_max_hull_point()
+                    },
+                    _ => 0.0,
+                };
+                self._progress_text = format!("{:.0}%", hull_ratio * 100.0);
+                if hull_ratio <= 0.0 {
+                    self._objective_state = ObjectiveState::Failed;
+                }
+            },
+        }
+    }
+}
+
+impl ObjectiveManager {
+    pub fn create_objective_manager() -> Box<ObjectiveManager> {
+        Box::new(ObjectiveManager {
+            _game_client: std::ptr::null(),
+            _objectives: Vec::new(),
+            _mission_time: 0.0,
+            _mission_result: MissionResult::InProgress,
+        })
+    }
+
+    pub fn initialize_objective_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+
+    pub fn destroy_objective_manager(&mut self) {
+        self.clear_objectives();
+    }
+
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
+    pub fn get_objectives(&self) -> &Vec<Objective> { &self._objectives }
+    pub fn get_mission_result(&self) -> MissionResult { self._mission_result }
+    pub fn get_mission_time(&self) -> f32 { self._mission_time }
+
+    pub fn start_objectives(&mut self, objective_datas: &Vec<ObjectiveData>) {
+        self.clear_objectives();
+        if objective_datas.is_empty() {
+            self._objectives.push(Objective::create_objective(&ObjectiveData {
+                _description: "Destroy all enemies".to_string(),
+                _objective_type: ObjectiveType::DestroyAllEnemies,
+            }));
+        } else {
+            let game_client = ptr_as_ref(self._game_client);
+            let actor_manager = game_client.get_actor_manager();
+            let level_data = game_client.get_project_scene_manager().get_level_data();
+            for objective_data in objective_datas.iter() {
+                match &objective_data._objective_type {
+                    ObjectiveType::DestroyTarget(actor_name) | ObjectiveType::ProtectAlly(actor_name) => {
+                        if false == actor_manager.is_actor_name_registered(actor_name) && false == ObjectiveManager::is_spawned_later(level_data, actor_name) {
+                            log::error!("objective {}: unknown actor {}", objective_data._description, actor_name);
+                        }
+                    },
+                    _ => (),
+                }
+                self._objectives.push(Objective::create_objective(objective_data));
+            }
+            if self._objectives.iter().all(|objective| objective.is_protect_objective()) {
+                log::error!("the mission has only the protect objectives and never completes");
+            }
+        }
+    }
+
+    // the named actor of a wave or a spawner spawn point
+    pub fn is_spawned_later(level_data: &LevelData, actor_name: &str) -> bool {
+        level_data._waves.iter().any(|wave_data| actor_name == wave_data._spawn_point_data._actor_name) ||
+            level_data._spawn_point_datas.iter().any(|spawn_point_type| match spawn_point_type {
+                SpawnPointType::Spawner(spawner_data) => actor_name == spawner_data._spawn_point_data._actor_name,
+                _ => false,
+            })
+    }
+
+    pub fn clear_objectives(&mut self) {
+        self._objectives.clear();
+        self._mission_time = 0.0;
+        self._mission_result = MissionResult::InProgress;
+    }
+
+    pub fn update_objective_manager(&mut self, delta_time: f32) {
+        if MissionResult::InProgress != self._mission_result {
+            return;
+        }
+
+        let game_client = ptr_as_ref(self._game_client);
+        let actor_manager = game_client.get_actor_manager();
+        if actor_manager.is_player_out_of_lives() {
+            self._mission_result = MissionResult::Failed;
+            return;
+        }
+
+        // the objectives keep running while the player waits for the respawn
+        if actor_manager.get_player_position().is_none() {
+            return;
+        }
+        let player_position = if actor_manager.is_player_dead() { None } else { actor_manager.get_player_position() };
+
+        self._mission_time += delta_time;
+        let has_pending_spawns = game_client.get_spawn_manager().has_pending_spawns();
+        for (objective_index, objective) in self._objectives.iter_mut().enumerate() {
+            let prev_objective_state = objective.get_objective_state();
+            objective.update_objective(actor_manager, player_position, has_pending_spawns, self._mission_time);
+            if prev_objective_state != objective.get_objective_state() && ObjectiveState::Completed == objective.get_objective_state() {
+                game_client.get_game_event_manager_mut().publish_event(GameEvent::ObjectiveCompleted { _objective_index: objective_index });
+            }
+        }
+
+        // the mission needs a completed objective, otherwise the protect objectives would complete it on the first frame
+        let has_completed_objective = self._objectives.iter().any(|objective| ObjectiveState::Completed == objective.get_objective_state());
+        if self._objectives.iter().any(|objective| ObjectiveState::Failed == objective.get_objective_state()) {
+            self._mission_result = MissionResult::Failed;
+        } else if has_completed_objective && self._objectives.iter().all(|objective| objective.is_satisfied()) {
+            self._mission_result = MissionResult::Complete;
+        }
+    }
+}
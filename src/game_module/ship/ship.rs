@@ -1,14 +1,19 @@
+use nalgebra::Vector3;
 use serde::{ Serialize, Deserialize };
 
+use rust_engine_3d::application::audio_manager::AudioLoop;
+use rust_engine_3d::effect::effect_data::EffectCreateInfo;
 use rust_engine_3d::renderer::render_object::{RenderObjectData, RenderObjectCreateInfo};
 use rust_engine_3d::renderer::transform_object::TransformObjectData;
 use rust_engine_3d::utilities::system::{RcRefCell, newRcRefCell, ptr_as_ref, ptr_as_mut};
 use crate::application::project_scene_manager::ProjectSceneManager;
-use crate::game_module::actor_manager::calc_floating_height;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actor_manager::{ActorHandle, calc_floating_height};
+use crate::game_module::actors::ai_controller::AIProfileData;
 use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::MAX_WEAPON_GROUP_COUNT;
 use crate::game_module::ship::ship_controller::{ShipController, ShipControllerData};
-use crate::game_module::weapons::weapon::{WeaponTrait, WeaponData, BeamEmitter, WeaponSlotData};
+use crate::game_module::weapons::weapon::{create_weapon, WeaponTrait, WeaponData, WeaponSlotData};
+use crate::resource::project_resource::DEFAULT_GAME_DATA_NAME;
 use rust_engine_3d::utilities::bounding_box::BoundingBox;
 
 #[derive(Serialize, Deserialize,Clone, Copy, Debug, PartialEq)]
@@ -17,6 +22,20 @@ pub enum ShipDataType {
     Tank,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShipDamageType {
+    Absorbed,
+    Penetrated,
+    Killed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShipDamageResult {
+    pub _damage_type: ShipDamageType,
+    pub _shield_damage: f32,
+    pub _hull_damage: f32,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct ShipDataCreateInfo {
@@ -26,8 +45,17 @@ pub struct ShipDataCreateInfo {
     pub _shield_armor: f32,
     pub _max_hull: f32,
     pub _max_shields: f32,
+    pub _shield_regen_delay: f32,
+    pub _shield_regen_rate: f32,
+    pub _shield_broken_recovery_time: f32,
     pub _weapon_solts: Vec<WeaponSlotData>,
     pub _controller_data_name: String,
+    pub _ai_profile_name: String,
+    pub _destruction_delay: f32,
+    pub _destruction_effects: Vec<String>,
+    pub _destruction_sound_bank: String,
+    pub _destruction_debris_effects: Vec<String>,
+    pub _destruction_debris_count: u32,
 }
 
 impl Default for ShipDataCreateInfo {
@@ -39,8 +67,17 @@ impl Default for ShipDataCreateInfo {
             _shield_armor: 0.0,
             _max_hull: 100.0,
             _max_shields: 10.0,
+            _shield_regen_delay: 3.0,
+            _shield_regen_rate: 2.0,
+            _shield_broken_recovery_time: 6.0,
             _weapon_solts: vec![WeaponSlotData::default()],
             _controller_data_name: "".to_string(),
+            _ai_profile_name: "".to_string(),
+            _destruction_delay: 2.0,
+            _destruction_effects: Vec::new(),
+            _destruction_sound_bank: "".to_string(),
+            _destruction_debris_effects: Vec::new(),
+            _destruction_debris_count: 0,
         }
     }
 }
@@ -54,24 +91,42 @@ pub struct ShipData {
     pub _shield_armor: f32,
     pub _max_hull: f32,
     pub _max_shields: f32,
+    pub _shield_regen_delay: f32,
+    pub _shield_regen_rate: f32,
+    pub _shield_broken_recovery_time: f32,
     pub _weapon_solts: Vec<WeaponSlotData>,
     pub _contoller_data: RcRefCell<ShipControllerData>,
+    pub _ai_profile_data: RcRefCell<AIProfileData>,
+    pub _destruction_delay: f32,
+    pub _destruction_effects: Vec<String>,
+    pub _destruction_sound_bank: String,
+    pub _destruction_debris_effects: Vec<String>,
+    pub _destruction_debris_count: u32,
 }
 
 pub struct ShipInstance {
     pub _ship_data: RcRefCell<ShipData>,
     pub _hull: f32,
     pub _shields: f32,
+    pub _shield_regen_timer: f32,
+    pub _is_shield_broken: bool,
     pub _render_object: RcRefCell<RenderObjectData>,
     pub _transform_object: *mut TransformObjectData,
     pub _controller: ShipController,
     pub _weapons: Vec<Box<dyn WeaponTrait>>,
+    pub _weapon_groups: Vec<Vec<*const dyn WeaponTrait>>,
+    pub _current_weapon_group: usize,
     pub _current_weapons: Vec<*const dyn WeaponTrait>,
 }
 
 // Implementation
 impl ShipData {
-    pub fn create_ship_data(ship_data_name: &str, ship_data_create_info: &ShipDataCreateInfo, controller_data: &RcRefCell<ShipControllerData>) -> RcRefCell<ShipData> {
+    pub fn create_ship_data(
+        ship_data_name: &str,
+        ship_data_create_info: &ShipDataCreateInfo,
+        controller_data: &RcRefCell<ShipControllerData>,
+        ai_profile_data: &RcRefCell<AIProfileData>
+    ) -> RcRefCell<ShipData> {
         newRcRefCell(ShipData {
             _ship_name: ship_data_name.to_string(),
             _ship_type: ship_data_create_info._ship_type,
@@ -80,8 +135,17 @@ impl ShipData {
             _shield_armor: ship_data_create_info._shield_armor,
             _max_hull: ship_data_create_info._max_hull,
             _max_shields: ship_data_create_info._max_shields,
+            _shield_regen_delay: ship_data_create_info._shield_regen_delay,
+            _shield_regen_rate: ship_data_create_info._shield_regen_rate,
+            _shield_broken_recovery_time: ship_data_create_info._shield_broken_recovery_time,
             _weapon_solts: ship_data_create_info._weapon_solts.clone(),
             _contoller_data: controller_data.clone(),
+            _ai_profile_data: ai_profile_data.clone(),
+            _destruction_delay: ship_data_create_info._destruction_delay,
+            _destruction_effects: ship_data_create_info._destruction_effects.clone(),
+            _destruction_sound_bank: ship_data_create_info._destruction_sound_bank.clone(),
+            _destruction_debris_effects: ship_data_create_info._destruction_debris_effects.clone(),
+            _destruction_debris_count: ship_data_create_info._destruction_debris_count,
         })
     }
 }
@@ -97,6 +161,8 @@ impl ShipInstance {
             _ship_data: ship_data.clone(),
             _hull: 0.0,
             _shields: 0.0,
+            _shield_regen_timer: 0.0,
+            _is_shield_broken: false,
             _render_object: render_object.clone(),
             _transform_object: (transform_object as *const TransformObjectData as *mut TransformObjectData).clone(),
             _controller: ShipController::create_ship_controller(
@@ -106,18 +172,23 @@ impl ShipInstance {
                 floating_height
             ),
             _weapons: Vec::new(),
+            _weapon_groups: Vec::new(),
+            _current_weapon_group: 0,
             _current_weapons: Vec::new(),
         }
     }
 
-    pub fn initialize_ship_instance(&mut self, owner_actor: *const ActorController, project_scene_manager: &mut ProjectSceneManager) {
+    pub fn initialize_ship_instance(&mut self, owner_actor_handle: ActorHandle, project_scene_manager: &mut ProjectSceneManager) {
         let ship_data = unsafe { &*self._ship_data.as_ptr() };
         self._hull = ship_data._max_hull;
         self._shields = ship_data._max_shields;
+        self._shield_regen_timer = 0.0;
+        self._is_shield_broken = false;
 
         // add weapons
         for weapon_slot in self._ship_data.borrow()._weapon_solts.iter() {
-            let weapon_data: RcRefCell<WeaponData> = project_scene_manager.get_project_resources().get_weapon_data("beam_emitter").clone();
+            let weapon_data_name = if weapon_slot._weapon_data_name.is_empty() { DEFAULT_GAME_DATA_NAME } else { &weapon_slot._weapon_data_name };
+            let weapon_data: RcRefCell<WeaponData> = project_scene_manager.get_project_resources().get_weapon_data(weapon_data_name).clone();
             let mut weapon_slot_transform = TransformObjectData::new_transform_object_data();
             weapon_slot_transform.set_position(&weapon_slot._position);
             weapon_slot_transform.set_rotation(&weapon_slot._rotation);
@@ -129,21 +200,37 @@ impl ShipInstance {
                 ..Default::default()
             };
             let weapon_render_object = project_scene_manager.add_skeletal_render_object("weapon", &render_object_create_info);
-            let weapon = BeamEmitter::create_beam_emitter(
-                owner_actor,
+            let mut weapon = create_weapon(
+                owner_actor_handle,
                 &weapon_data,
                 &weapon_slot_transform,
                 &weapon_render_object,
             );
-            self._current_weapons.push(weapon.as_ref());
+            weapon.initialize_weapon();
+
+            // regist weapon to the fire group
+            let mut fire_group = weapon_slot._fire_group;
+            if MAX_WEAPON_GROUP_COUNT <= fire_group {
+                log::error!("weapon slot {}: fire group {} is clamped to {}", weapon_data_name, fire_group, MAX_WEAPON_GROUP_COUNT - 1);
+                fire_group = MAX_WEAPON_GROUP_COUNT - 1;
+            }
+            if self._weapon_groups.len() <= fire_group {
+                self._weapon_groups.resize(fire_group + 1, Vec::new());
+            }
+            self._weapon_groups[fire_group].push(weapon.as_ref());
             self._weapons.push(weapon);
         }
+        if let Some(weapon_group) = self._weapon_groups.iter().position(|weapons| false == weapons.is_empty()) {
+            self.set_current_weapon_group(weapon_group);
+        }
     }
     pub fn remove_ship_instance(&mut self, project_scene_manager: &mut ProjectSceneManager) {
         project_scene_manager.remove_skeletal_render_object(&self._render_object.borrow()._render_object_name);
         for weapon in self._weapons.iter_mut() {
             weapon.remove_weapon(project_scene_manager);
         }
+        self._current_weapons.clear();
+        self._weapon_groups.clear();
         self._weapons.clear();
     }
     pub fn get_ship_data(&self) -> &ShipData { ptr_as_ref(self._ship_data.as_ptr()) }
@@ -157,6 +244,43 @@ impl ShipInstance {
     pub fn get_current_weapons(&self) -> &Vec<*const dyn WeaponTrait> {
         &self._current_weapons
     }
+    pub fn get_weapon_group_count(&self) -> usize {
+        self._weapon_groups.len()
+    }
+    pub fn get_current_weapon_group(&self) -> usize {
+        self._current_weapon_group
+    }
+    pub fn set_current_weapon_group(&mut self, weapon_group: usize) {
+        if weapon_group < self._weapon_groups.len() && false == self._weapon_groups[weapon_group].is_empty() {
+            self._current_weapon_group = weapon_group;
+            self._current_weapons = self._weapon_groups[weapon_group].clone();
+        }
+    }
+    pub fn next_weapon_group(&mut self) {
+        let weapon_group_count = self._weapon_groups.len();
+        for i in 1..weapon_group_count {
+            let weapon_group = (self._current_weapon_group + i) % weapon_group_count;
+            if false == self._weapon_groups[weapon_group].is_empty() {
+                self.set_current_weapon_group(weapon_group);
+                break;
+            }
+        }
+    }
+    pub fn prev_weapon_group(&mut self) {
+        let weapon_group_count = self._weapon_groups.len();
+        for i in 1..weapon_group_count {
+            let weapon_group = (self._current_weapon_group + weapon_group_count - i) % weapon_group_count;
+            if false == self._weapon_groups[weapon_group].is_empty() {
+                self.set_current_weapon_group(weapon_group);
+                break;
+            }
+        }
+    }
+    pub fn set_aim_position(&mut self, aim_position: Option<&Vector3<f32>>) {
+        for weapon in self._weapons.iter_mut() {
+            weapon.get_weapon_base_mut().set_aim_position(aim_position);
+        }
+    }
     pub fn get_hull_point(&self) -> f32 {
         self._hull
     }
@@ -169,6 +293,116 @@ impl ShipInstance {
     pub fn get_max_shield_point(&self) -> f32 {
         self.get_ship_data()._max_shields
     }
+    pub fn is_shield_broken(&self) -> bool {
+        self._is_shield_broken
+    }
+    pub fn is_destroyed(&self) -> bool {
+        self._hull <= 0.0
+    }
+    pub fn apply_damage(&mut self, shield_damage: f32, hull_damage: f32) -> Option<ShipDamageResult> {
+        if self.is_destroyed() {
+            return None;
+        }
+
+        let hull_armor = self.get_ship_data()._hull_armor;
+        let shield_armor = self.get_ship_data()._shield_armor;
+
+        // any hit delays the shield regeneration
+        self._shield_regen_timer = 0.0;
+
+        // shields absorb up to their remaining points, the overflow reaches the hull
+        let mut hull_damage_ratio: f32 = 1.0;
+        let mut applied_shield_damage: f32 = 0.0;
+        if 0.0 < self._shields {
+            let shield_damage = 0f32.max(shield_damage - shield_armor);
+            applied_shield_damage = shield_damage.min(self._shields);
+            self._shields -= applied_shield_damage;
+            // a hit without shield damage left after the armor is not absorbed by the shields
+            if 0.0 < shield_damage {
+                hull_damage_ratio = (shield_damage - applied_shield_damage) / shield_damage;
+            }
+            if self._shields <= 0.0 {
+                self._shields = 0.0;
+                self._is_shield_broken = true;
+            }
+        }
+
+        let mut applied_hull_damage: f32 = 0.0;
+        if 0.0 < hull_damage_ratio {
+            applied_hull_damage = 0f32.max(hull_damage * hull_damage_ratio - hull_armor).min(self._hull);
+            self._hull -= applied_hull_damage;
+        }
+
+        let damage_type = if self.is_destroyed() {
+            self._hull = 0.0;
+            ShipDamageType::Killed
+        } else if 0.0 < applied_hull_damage {
+            ShipDamageType::Penetrated
+        } else {
+            ShipDamageType::Absorbed
+        };
+
+        Some(ShipDamageResult {
+            _damage_type: damage_type,
+            _shield_damage: applied_shield_damage,
+            _hull_damage: applied_hull_damage,
+        })
+    }
+    pub fn update_shields(&mut self, delta_time: f32) {
+        let ship_data = ptr_as_ref(self._ship_data.as_ptr());
+        if self.is_destroyed() || ship_data._max_shields <= self._shields {
+            return;
+        }
+
+        self._shield_regen_timer += delta_time;
+        let regen_delay = if self._is_shield_broken { ship_data._shield_broken_recovery_time } else { ship_data._shield_regen_delay };
+        if regen_delay <= self._shield_regen_timer {
+            self._is_shield_broken = false;
+            self._shields = ship_data._max_shields.min(self._shields + ship_data._shield_regen_rate * delta_time);
+        }
+    }
+    // the explosion at the center and the debris scattered inside of the bounding sphere
+    pub fn play_destruction_effects(&self, game_client: &GameClient) {
+        let ship_data = self.get_ship_data();
+        let project_scene_manager = game_client.get_project_scene_manager_mut();
+        let position = self.get_transform().get_position();
+        let rotation = self.get_transform().get_rotation();
+        let destruction_effect_count = ship_data._destruction_effects.len();
+        if 0 < destruction_effect_count {
+            let effect_index: usize = if 1 < destruction_effect_count { rand::random::<usize>() % destruction_effect_count } else { 0 };
+            let effect_create_info = EffectCreateInfo {
+                _effect_position: position.clone_owned(),
+                _effect_rotation: rotation.clone_owned(),
+                _effect_data_name: ship_data._destruction_effects[effect_index].clone(),
+                ..Default::default()
+            };
+            project_scene_manager.add_effect(&effect_create_info._effect_data_name, &effect_create_info);
+        }
+
+        let debris_effect_count = ship_data._destruction_debris_effects.len();
+        if 0 < debris_effect_count {
+            let debris_radius = self.get_bound_box()._radius;
+            for _ in 0..ship_data._destruction_debris_count {
+                let offset = Vector3::new(
+                    rand::random::<f32>() * 2.0 - 1.0,
+                    rand::random::<f32>() * 2.0 - 1.0,
+                    rand::random::<f32>() * 2.0 - 1.0
+                ) * debris_radius;
+                let effect_index: usize = rand::random::<usize>() % debris_effect_count;
+                let effect_create_info = EffectCreateInfo {
+                    _effect_position: position + offset,
+                    _effect_rotation: rotation.clone_owned(),
+                    _effect_data_name: ship_data._destruction_debris_effects[effect_index].clone(),
+                    ..Default::default()
+                };
+                project_scene_manager.add_effect(&effect_create_info._effect_data_name, &effect_create_info);
+            }
+        }
+
+        if false == ship_data._destruction_sound_bank.is_empty() {
+            game_client.get_audio_manager_mut().create_audio_instance_from_bank(&ship_data._destruction_sound_bank, AudioLoop::ONCE);
+        }
+    }
     pub fn ship_fire(&mut self, game_client: &GameClient) {
         for weapon in self._current_weapons.iter() {
             let weapon: &mut dyn WeaponTrait = unsafe { &mut *(*weapon as *mut dyn WeaponTrait) };
@@ -184,6 +418,8 @@ impl ShipInstance {
         ship_transform.set_position(self._controller.get_position());
         ship_transform.update_matrix();
 
+        self.update_shields(delta_time);
+
         for weapon in self._weapons.iter_mut() {
             weapon.update_weapon(ship_transform, delta_time);
         }
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use nalgebra::Vector3;
+
+
+type CellKey = (i32, i32, i32);
+
+// Uniform grid broadphase. It is rebuilt every frame from the bounding spheres of the items,
+// and the queries return the candidates only once even if they are registered in several cells.
+pub struct SpatialGrid<T: Copy> {
+    pub _cell_size: f32,
+    pub _cells: HashMap<CellKey, Vec<usize>>,
+    pub _items: Vec<T>,
+    pub _item_query_ids: Vec<u32>,
+    pub _query_id: u32,
+}
+
+impl<T: Copy> SpatialGrid<T> {
+    pub fn create_spatial_grid(cell_size: f32) -> SpatialGrid<T> {
+        SpatialGrid {
+            _cell_size: cell_size,
+            _cells: HashMap::new(),
+            _items: Vec::new(),
+            _item_query_ids: Vec::new(),
+            _query_id: 0,
+        }
+    }
+
+    pub fn clear_spatial_grid(&mut self) {
+        // keep the allocations of the cells used in the last frame, and evict the others
+        // so the cells do not pile up as the actors move across the world
+        self._cells.retain(|_, cell| {
+            let is_used = false == cell.is_empty();
+            cell.clear();
+            is_used
+        });
+        self._items.clear();
+        self._item_query_ids.clear();
+    }
+
+    pub fn get_item_count(&self) -> usize {
+        self._items.len()
+    }
+
+    fn get_cell_key(&self, position: &Vector3<f32>) -> CellKey {
+        (
+            (position.x / self._cell_size).floor() as i32,
+            (position.y / self._cell_size).floor() as i32,
+            (position.z / self._cell_size).floor() as i32,
+        )
+    }
+
+    pub fn insert_item(&mut self, item: T, center: &Vector3<f32>, radius: f32) {
+        let item_index = self._items.len();
+        self._items.push(item);
+        self._item_query_ids.push(0);
+
+        let extent = Vector3::new(radius, radius, radius);
+        let min_key = self.get_cell_key(&(center - &extent));
+        let max_key = self.get_cell_key(&(center + &extent));
+        for x in min_key.0..=max_key.0 {
+            for y in min_key.1..=max_key.1 {
+                for z in min_key.2..=max_key.2 {
+                    self._cells.entry((x, y, z)).or_insert_with(Vec::new).push(item_index);
+                }
+            }
+        }
+    }
+
+    fn next_query_id(&mut self) -> u32 {
+        self._query_id = self._query_id.wrapping_add(1);
+        if 0 == self._query_id {
+            // the query ids of the items may be stale after wrapping around
+            for query_id in self._item_query_ids.iter_mut() {
+                *query_id = 0;
+            }
+            self._query_id = 1;
+        }
+        self._query_id
+    }
+
+    pub fn query_bound(&mut self, bound_min: &Vector3<f32>, bound_max: &Vector3<f32>, candidates: &mut Vec<T>) {
+        candidates.clear();
+        let query_id = self.next_query_id();
+        let min_key = self.get_cell_key(bound_min);
+        let max_key = self.get_cell_key(bound_max);
+        for x in min_key.0..=max_key.0 {
+            for y in min_key.1..=max_key.1 {
+                for z in min_key.2..=max_key.2 {
+                    if let Some(cell) = self._cells.get(&(x, y, z)) {
+                        for item_index in cell.iter() {
+                            if query_id != self._item_query_ids[*item_index] {
+                                self._item_query_ids[*item_index] = query_id;
+                                candidates.push(self._items[*item_index]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn query_sphere(&mut self, center: &Vector3<f32>, radius: f32, candidates: &mut Vec<T>) {
+        let extent = Vector3::new(radius, radius, radius);
+        self.query_bound(&(center - &extent), &(center + &extent), candidates);
+    }
+
+    pub fn query_segment(&mut self, start: &Vector3<f32>, end: &Vector3<f32>, candidates: &mut Vec<T>) {
+        // bullets move a short distance per frame, so the bound of the segment covers only a few cells
+        self.query_bound(&start.inf(end), &start.sup(end), candidates);
+    }
+}
@@ -0,0 +1,202 @@
+use nalgebra::Vector3;
+
+use rust_engine_3d::utilities::math::TWO_PI;
+use rust_engine_3d::utilities::system::{ptr_as_ref, ptr_as_mut};
+use crate::game_module::actor_manager::{ActorHandle, ActorManager};
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::MIN_INFINITE_SPAWNER_INTERVAL;
+use crate::game_module::level_datas::level_data::LevelData;
+use crate::game_module::level_datas::spawn_point::{ShipSpawnPointData, SpawnPointType, SpawnerData, WaveData};
+
+// runtime of the waves and the spawner spawn points
+pub struct Spawner {
+    pub _spawn_point_data: ShipSpawnPointData,
+    pub _delay: f32,
+    pub _interval: f32,
+    // 0 spawns forever
+    pub _count: u32,
+    // 0 is unlimited
+    pub _max_alive_count: u32,
+    pub _spawn_radius: f32,
+    // the spawner waits until the trigger spawner is cleared
+    pub _trigger_spawner_index: Option<usize>,
+    pub _is_triggered: bool,
+    pub _elapsed_time: f32,
+    pub _next_spawn_time: f32,
+    pub _spawn_count: u32,
+    pub _alive_actor_handles: Vec<ActorHandle>,
+}
+
+pub struct SpawnManager {
+    pub _game_client: *const GameClient,
+    pub _spawners: Vec<Spawner>,
+}
+
+impl Spawner {
+    pub fn create_spawner_from_wave(wave_data: &WaveData, trigger_spawner_index: Option<usize>) -> Spawner {
+        // a wave always ends, only the spawner spawn points spawn forever
+        if 0 == wave_data._count {
+            log::error!("wave of {}: the count is 0, spawns 1", wave_data._spawn_point_data._ship_data_name);
+        }
+        Spawner::create_spawner(
+            &wave_data._spawn_point_data,
+            wave_data._delay,
+            wave_data._interval,
+            wave_data._count.max(1),
+            0,
+            wave_data._spawn_radius,
+            trigger_spawner_index
+        )
+    }
+
+    pub fn create_spawner_from_spawner_data(spawner_data: &SpawnerData) -> Spawner {
+        let mut interval = spawner_data._interval;
+        if 0 == spawner_data._count && interval < MIN_INFINITE_SPAWNER_INTERVAL {
+            log::error!("spawner of {}: the interval {} of the infinite spawner is clamped to {}", spawner_data._spawn_point_data._ship_data_name, interval, MIN_INFINITE_SPAWNER_INTERVAL);
+            interval = MIN_INFINITE_SPAWNER_INTERVAL;
+        }
+        Spawner::create_spawner(
+            &spawner_data._spawn_point_data,
+            spawner_data._delay,
+            interval,
+            spawner_data._count,
+            spawner_data._max_alive_count,
+            spawner_data._spawn_radius,
+            None
+        )
+    }
+
+    pub fn create_spawner(
+        spawn_point_data: &ShipSpawnPointData,
+        delay: f32,
+        interval: f32,
+        count: u32,
+        max_alive_count: u32,
+        spawn_radius: f32,
+        trigger_spawner_index: Option<usize>
+    ) -> Spawner {
+        Spawner {
+            _spawn_point_data: spawn_point_data.clone(),
+            _delay: delay,
+            _interval: interval,
+            _count: count,
+            _max_alive_count: max_alive_count,
+            _spawn_radius: spawn_radius,
+            _trigger_spawner_index: trigger_spawner_index,
+            _is_triggered: false,
+            _elapsed_time: 0.0,
+            _next_spawn_time: delay,
+            _spawn_count: 0,
+            _alive_actor_handles: Vec::new(),
+        }
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        0 == self._count
+    }
+
+    pub fn is_finished(&self) -> bool {
+        0 != self._count && self._count <= self._spawn_count
+    }
+
+    pub fn is_cleared(&self) -> bool {
+        self.is_finished() && self._alive_actor_handles.is_empty()
+    }
+
+    pub fn can_spawn(&self) -> bool {
+        self._is_triggered &&
+            false == self.is_finished() &&
+            self._next_spawn_time <= self._elapsed_time &&
+            (0 == self._max_alive_count || (self._alive_actor_handles.len() as u32) < self._max_alive_count)
+    }
+
+    pub fn update_alive_actors(&mut self, actor_manager: &ActorManager) {
+        self._alive_actor_handles.retain(|actor_handle| match actor_manager.get_actor(*actor_handle) {
+            Some(actor) => false == actor.get_ship().is_destroyed(),
+            None => false,
+        });
+    }
+
+    pub fn spawn_actor(&mut self, game_client: &GameClient) {
+        let angle = rand::random::<f32>() * TWO_PI;
+        let radius = self._spawn_radius * rand::random::<f32>().sqrt();
+        let spawn_point_data = ShipSpawnPointData {
+            _position: &self._spawn_point_data._position + Vector3::new(angle.sin() * radius, 0.0, angle.cos() * radius),
+            ..self._spawn_point_data.clone()
+        };
+        let actor_handle = game_client.get_actor_manager_mut().create_actor(game_client, &spawn_point_data, false);
+        self._alive_actor_handles.push(actor_handle);
+        self._spawn_count += 1;
+        self._next_spawn_time = self._elapsed_time + self._interval;
+    }
+}
+
+impl SpawnManager {
+    pub fn create_spawn_manager() -> Box<SpawnManager> {
+        Box::new(SpawnManager {
+            _game_client: std::ptr::null(),
+            _spawners: Vec::new(),
+        })
+    }
+
+    pub fn initialize_spawn_manager(&mut self, game_client: &GameClient) {
+        self._game_client = game_client;
+    }
+
+    pub fn destroy_spawn_manager(&mut self) {
+        self.clear_spawners();
+    }
+
+    pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
+    pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
+
+    pub fn start_spawners(&mut self, level_data: &LevelData) {
+        self.clear_spawners();
+        for spawn_point_type in level_data._spawn_point_datas.iter() {
+            if let SpawnPointType::Spawner(spawner_data) = spawn_point_type {
+                self._spawners.push(Spawner::create_spawner_from_spawner_data(spawner_data));
+            }
+        }
+
+        let mut previous_wave_index: Option<usize> = None;
+        for wave_data in level_data._waves.iter() {
+            let trigger_spawner_index = if wave_data._wait_previous_wave_cleared { previous_wave_index } else { None };
+            previous_wave_index = Some(self._spawners.len());
+            self._spawners.push(Spawner::create_spawner_from_wave(wave_data, trigger_spawner_index));
+        }
+    }
+
+    pub fn clear_spawners(&mut self) {
+        self._spawners.clear();
+    }
+
+    // the infinite spawners are never finished, so they do not hold back the objectives
+    pub fn has_pending_spawns(&self) -> bool {
+        self._spawners.iter().any(|spawner| false == spawner.is_infinite() && false == spawner.is_finished())
+    }
+
+    pub fn update_spawn_manager(&mut self, delta_time: f32) {
+        let game_client = ptr_as_ref(self._game_client);
+        let actor_manager = game_client.get_actor_manager();
+        for spawner in self._spawners.iter_mut() {
+            spawner.update_alive_actors(actor_manager);
+        }
+
+        for spawner_index in 0..self._spawners.len() {
+            if false == self._spawners[spawner_index]._is_triggered {
+                self._spawners[spawner_index]._is_triggered = match self._spawners[spawner_index]._trigger_spawner_index {
+                    Some(trigger_spawner_index) => self._spawners[trigger_spawner_index].is_cleared(),
+                    None => true,
+                };
+            }
+
+            let spawner = &mut self._spawners[spawner_index];
+            if spawner._is_triggered {
+                spawner._elapsed_time += delta_time;
+                if spawner.can_spawn() {
+                    spawner.spawn_actor(game_client);
+                }
+            }
+        }
+    }
+}
@@ -62,7 +62,7 @@ impl HullPointWidget {
     }
 
     pub fn update_hull_point_widget(&self, hull_point: f32, max_hull_point: f32) {
-        let hull_point_ratio = 1.0f32.min(hull_point / max_hull_point);
+        let hull_point_ratio = if 0.0 < max_hull_point { 1.0f32.min(hull_point / max_hull_point) } else { 0.0 };
         let hull_point_ui = ptr_as_mut(self._hull_point_layer).get_ui_component_mut();
         hull_point_ui.set_text(&format!("Hull: {}", hull_point as i32));
         let hull_point_bar = ptr_as_mut(self._hull_point_bar).get_ui_component_mut();
@@ -81,7 +81,8 @@ impl ShieldPointWidget {
     }
 
     pub fn update_shield_point_widget(&self, shield_point: f32, max_shield_point: f32) {
-        let shield_point_ratio = 1.0f32.min(shield_point / max_shield_point);
+        // a ship without the shield shows an empty bar
+        let shield_point_ratio = if 0.0 < max_shield_point { 1.0f32.min(shield_point / max_shield_point) } else { 0.0 };
         let shield_point_ui = ptr_as_mut(self._shield_point_layer).get_ui_component_mut();
         shield_point_ui.set_text(&format!("Shield: {}", shield_point as i32));
         let shield_point_bar = ptr_as_mut(self._shield_point_bar).get_ui_component_mut();
@@ -5,6 +5,8 @@ use nalgebra::Vector2;
 use rust_engine_3d::renderer::ui::*;
 use rust_engine_3d::resource::resource::ProjectResourcesBase;
 use rust_engine_3d::vulkan_context::vulkan_context::get_color32;
+use crate::game_module::game_constants::{MAX_OBJECTIVE_HUD_COUNT, TARGET_HUD_OFFSET};
+use crate::game_module::objective_manager::{Objective, ObjectiveState};
 use crate::game_module::ui_widgets::hit_point_widgets::{ShieldPointWidget, HullPointWidget};
 use crate::resource::project_resource::ProjectResources;
 use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
@@ -20,6 +22,8 @@ pub struct TargetHud {
 
 pub struct PlayerHud {
     pub _widget: *const WidgetDefault,
+    pub _weapon_group: *const WidgetDefault,
+    pub _lives: *const WidgetDefault,
     pub _hull_point_widget: HullPointWidget,
     pub _shield_point_widget: ShieldPointWidget,
 }
@@ -30,10 +34,21 @@ pub struct CrossHair {
     pub _tracking_mouse: bool,
 }
 
+pub struct ObjectiveHud {
+    pub _widget: *const WidgetDefault,
+    pub _objective_widgets: Vec<*const WidgetDefault>,
+}
+
+pub struct MessageBox {
+    pub _widget: *const WidgetDefault,
+}
+
 pub struct SelectionArea {
     pub _selection_area_layout: Rc<dyn Widget>,
     pub _selection_widget: Rc<dyn Widget>,
     pub _drag_mouse: bool,
+    // the rectangle of the finished drag in the screen space, taken by the game controller
+    pub _selection_rect: Option<(Vector2<f32>, Vector2<f32>)>,
 }
 
 // CrossHair
@@ -77,12 +92,14 @@ impl TargetHud {
         ui_component.set_expandable(true);
         ui_component.set_padding(hud_layer_padding);
         ui_component.set_color(get_color32(255, 255, 255, 10));
+        ui_component.set_border(2.0);
         ui_component.set_opacity(0.5);
+        ui_component.set_visible(false);
         root_widget.add_widget(&target_widget);
 
         let target_distance = UIManager::create_widget("target_distance", UIWidgetTypes::Default);
         let ui_component = ptr_as_mut(target_distance.as_ref()).get_ui_component_mut();
-        ui_component.set_text("100m");
+        ui_component.set_text("0m");
         ui_component.set_size(hud_ui_width, hud_ui_height);
         ui_component.set_halign(HorizontalAlign::LEFT);
         ui_component.set_valign(VerticalAlign::CENTER);
@@ -100,6 +117,20 @@ impl TargetHud {
             _shield_point_widget: ShieldPointWidget::create_shield_point_widget(ptr_as_mut(target_widget.as_ref()))
         }
     }
+
+    pub fn show_target_hud(&self, show: bool) {
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        ui_component.set_visible(show);
+    }
+
+    // the panel is pinned to the right of the screen bound of the target
+    pub fn update_target_hud(&self, screen_min: &Vector2<f32>, screen_max: &Vector2<f32>, distance: f32, relation_color: u32) {
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        ui_component.set_pos(screen_max.x + TARGET_HUD_OFFSET, screen_min.y);
+        ui_component.set_border_color(relation_color);
+        let ui_component = ptr_as_mut(self._distance).get_ui_component_mut();
+        ui_component.set_text(&format!("{:.0}m", distance));
+    }
 }
 
 // PlayerHud
@@ -108,6 +139,10 @@ impl PlayerHud {
         let hud_layer_width: f32 = 100.0;
         let hud_layer_height: f32 = 100.0;
         let hud_layer_padding: f32 = 10.0;
+        let hud_ui_width: f32 = 100.0;
+        let hud_ui_height: f32 = 25.0;
+        let hud_ui_margine: f32 = 2.0;
+        let hud_ui_padding: f32 = 4.0;
 
         let player_widget = UIManager::create_widget("player_widget", UIWidgetTypes::Default);
         let ui_component = ptr_as_mut(player_widget.as_ref()).get_ui_component_mut();
@@ -120,14 +155,169 @@ impl PlayerHud {
         ui_component.set_expandable(true);
         ui_component.set_padding(hud_layer_padding);
         ui_component.set_color(get_color32(255, 255, 255, 10));
+        ui_component.set_border(2.0);
         root_widget.add_widget(&player_widget);
 
+        let weapon_group = UIManager::create_widget("weapon_group", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(weapon_group.as_ref()).get_ui_component_mut();
+        ui_component.set_text("Group 1");
+        ui_component.set_size(hud_ui_width, hud_ui_height);
+        ui_component.set_halign(HorizontalAlign::LEFT);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_color(get_color32(0, 0, 255, 20));
+        ui_component.set_font_color(get_color32(255, 255, 255, 255));
+        ui_component.set_margine(hud_ui_margine);
+        ui_component.set_padding(hud_ui_padding);
+        ui_component.set_expandable(true);
+        ptr_as_mut(player_widget.as_ref()).add_widget(&weapon_group);
+
+        let lives = UIManager::create_widget("lives", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(lives.as_ref()).get_ui_component_mut();
+        ui_component.set_text("Lives");
+        ui_component.set_size(hud_ui_width, hud_ui_height);
+        ui_component.set_halign(HorizontalAlign::LEFT);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_color(get_color32(0, 0, 255, 20));
+        ui_component.set_font_color(get_color32(255, 255, 255, 255));
+        ui_component.set_margine(hud_ui_margine);
+        ui_component.set_padding(hud_ui_padding);
+        ui_component.set_expandable(true);
+        ptr_as_mut(player_widget.as_ref()).add_widget(&lives);
+
         PlayerHud {
             _widget: player_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _weapon_group: weapon_group.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _lives: lives.as_ref() as *const dyn Widget as *const WidgetDefault,
             _hull_point_widget: HullPointWidget::create_hull_point_widget(ptr_as_mut(player_widget.as_ref())),
             _shield_point_widget: ShieldPointWidget::create_shield_point_widget(ptr_as_mut(player_widget.as_ref())),
         }
     }
+
+    pub fn update_faction_color(&self, faction_color: u32) {
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        ui_component.set_border_color(faction_color);
+    }
+
+    pub fn update_weapon_group_widget(&self, weapon_group: usize, weapon_group_count: usize) {
+        let ui_component = ptr_as_mut(self._weapon_group).get_ui_component_mut();
+        ui_component.set_text(&format!("Group {}/{}", weapon_group + 1, weapon_group_count));
+    }
+
+    pub fn update_lives_widget(&self, lives: u32, is_player_dead: bool, respawn_time: f32) {
+        let ui_component = ptr_as_mut(self._lives).get_ui_component_mut();
+        if is_player_dead && 0 < lives {
+            ui_component.set_text(&format!("Respawn {:.0}", respawn_time.ceil()));
+        } else {
+            ui_component.set_text(&format!("Lives {}", lives));
+        }
+    }
+}
+
+
+// ObjectiveHud
+impl ObjectiveHud {
+    pub fn create_objective_hud(root_widget: &mut dyn Widget, pos: &Vector2<f32>) -> ObjectiveHud {
+        let hud_layer_width: f32 = 300.0;
+        let hud_layer_height: f32 = 30.0;
+        let hud_layer_padding: f32 = 10.0;
+        let hud_ui_width: f32 = 300.0;
+        let hud_ui_height: f32 = 25.0;
+        let hud_ui_margine: f32 = 2.0;
+        let hud_ui_padding: f32 = 4.0;
+
+        let objective_widget = UIManager::create_widget("objective_widget", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(objective_widget.as_ref()).get_ui_component_mut();
+        ui_component.set_size(hud_layer_width, hud_layer_height);
+        ui_component.set_pos(pos.x, pos.y);
+        ui_component.set_layout_type(UILayoutType::BoxLayout);
+        ui_component.set_layout_orientation(Orientation::VERTICAL);
+        ui_component.set_halign(HorizontalAlign::LEFT);
+        ui_component.set_valign(VerticalAlign::TOP);
+        ui_component.set_expandable(true);
+        ui_component.set_padding(hud_layer_padding);
+        ui_component.set_color(get_color32(255, 255, 255, 10));
+        root_widget.add_widget(&objective_widget);
+
+        // the lines are created up front and the unused lines are hidden
+        let mut objective_widgets: Vec<*const WidgetDefault> = Vec::new();
+        for _ in 0..MAX_OBJECTIVE_HUD_COUNT {
+            let objective_line = UIManager::create_widget("objective_line", UIWidgetTypes::Default);
+            let ui_component = ptr_as_mut(objective_line.as_ref()).get_ui_component_mut();
+            ui_component.set_size(hud_ui_width, hud_ui_height);
+            ui_component.set_halign(HorizontalAlign::LEFT);
+            ui_component.set_valign(VerticalAlign::CENTER);
+            ui_component.set_color(get_color32(0, 0, 0, 20));
+            ui_component.set_font_color(get_color32(255, 255, 255, 255));
+            ui_component.set_margine(hud_ui_margine);
+            ui_component.set_padding(hud_ui_padding);
+            ui_component.set_expandable(true);
+            ui_component.set_visible(false);
+            ptr_as_mut(objective_widget.as_ref()).add_widget(&objective_line);
+            objective_widgets.push(objective_line.as_ref() as *const dyn Widget as *const WidgetDefault);
+        }
+
+        ObjectiveHud {
+            _widget: objective_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+            _objective_widgets: objective_widgets,
+        }
+    }
+
+    pub fn update_objective_hud(&self, objectives: &Vec<Objective>) {
+        for (index, objective_widget) in self._objective_widgets.iter().enumerate() {
+            let ui_component = ptr_as_mut(*objective_widget).get_ui_component_mut();
+            if let Some(objective) = objectives.get(index) {
+                let (state_text, font_color) = match objective.get_objective_state() {
+                    ObjectiveState::InProgress => ("[ ]", get_color32(255, 255, 255, 255)),
+                    ObjectiveState::Completed => ("[v]", get_color32(0, 255, 0, 255)),
+                    ObjectiveState::Failed => ("[x]", get_color32(255, 0, 0, 255)),
+                };
+                ui_component.set_text(&format!("{} {} {}", state_text, objective.get_description(), objective.get_progress_text()));
+                ui_component.set_font_color(font_color);
+                ui_component.set_visible(true);
+            } else {
+                ui_component.set_visible(false);
+            }
+        }
+    }
+}
+
+
+// MessageBox
+impl MessageBox {
+    pub fn create_message_box(root_widget: &mut dyn Widget, window_center: &Vector2<f32>) -> MessageBox {
+        let message_box_width: f32 = 600.0;
+        let message_box_height: f32 = 60.0;
+
+        let message_box_widget = UIManager::create_widget("message_box", UIWidgetTypes::Default);
+        let ui_component = ptr_as_mut(message_box_widget.as_ref()).get_ui_component_mut();
+        ui_component.set_size(message_box_width, message_box_height);
+        ui_component.set_center(window_center.x, window_center.y);
+        ui_component.set_halign(HorizontalAlign::CENTER);
+        ui_component.set_valign(VerticalAlign::CENTER);
+        ui_component.set_font_size(24.0);
+        ui_component.set_color(get_color32(0, 0, 0, 128));
+        ui_component.set_font_color(get_color32(255, 255, 255, 255));
+        ui_component.set_border_color(get_color32(255, 255, 255, 128));
+        ui_component.set_border(2.0);
+        ui_component.set_round(10.0);
+        ui_component.set_visible(false);
+        root_widget.add_widget(&message_box_widget);
+
+        MessageBox {
+            _widget: message_box_widget.as_ref() as *const dyn Widget as *const WidgetDefault,
+        }
+    }
+
+    pub fn show_message(&self, message: &str) {
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        ui_component.set_text(message);
+        ui_component.set_visible(true);
+    }
+
+    pub fn hide_message(&self) {
+        let ui_component = ptr_as_mut(self._widget).get_ui_component_mut();
+        ui_component.set_visible(false);
+    }
 }
 
 
@@ -164,6 +354,7 @@ impl SelectionArea {
             _selection_area_layout: selection_area_layout,
             _selection_widget: selection_widget,
             _drag_mouse: false,
+            _selection_rect: None,
         });
 
         // set user data
@@ -196,7 +387,10 @@ impl SelectionArea {
     }
 
     pub fn touch_up(ui_component: &mut UIComponentInstance, touched_pos: &Vector2<f32>, _touched_pos_delta: &Vector2<f32>) -> bool {
-        let selection_area = ptr_as_ref(ui_component.get_user_data() as *const SelectionArea);
+        let touch_start_pos: Vector2<f32> = ui_component.get_touch_start_pos().clone_owned();
+        let selection_area = ptr_as_mut(ui_component.get_user_data() as *const SelectionArea);
+        selection_area._selection_rect = Some((touch_start_pos.inf(touched_pos), touch_start_pos.sup(touched_pos)));
+
         let selection_widget = selection_area._selection_widget.as_ref();
         let selection_ui_component = ptr_as_mut(selection_widget).get_ui_component_mut();
         selection_ui_component.set_pos(touched_pos.x, touched_pos.y);
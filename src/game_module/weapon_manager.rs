@@ -1,129 +1,353 @@
 use std::collections::HashMap;
-use std::rc::Rc;
+
+use nalgebra::Vector3;
 
 use rust_engine_3d::application::audio_manager::AudioLoop;
 use rust_engine_3d::effect::effect_data::EffectCreateInfo;
 use rust_engine_3d::renderer::render_object::RenderObjectCreateInfo;
 use rust_engine_3d::utilities::system::{ptr_as_mut, ptr_as_ref};
+use crate::game_module::actor_manager::ActorHandle;
+use crate::game_module::collision::{CollisionHit, get_nearest_collision_hit, intersect_segment_bound_box, intersect_segment_height_plane};
+use crate::game_module::faction::FactionId;
 use crate::game_module::game_client::GameClient;
-use crate::game_module::weapons::bullet::Bullet;
+use crate::game_module::game_event_manager::GameEvent;
+use crate::game_module::game_constants::{BULLET_POOL_ALLOCATION_COUNT, HITSCAN_BEAM_MIN_TIME, MAX_BULLET_COUNT};
+use crate::game_module::ship::ship::{ShipDamageType, ShipDamageResult};
+use crate::game_module::weapons::bullet::{Bullet, BulletData};
 use crate::game_module::weapons::weapon::WeaponTrait;
 
 
+pub type BulletHandle = usize;
+
+// the bullet model stretched along the segment of a hitscan shot
+pub struct HitscanBeam {
+    pub _render_object_handle: usize,
+    pub _model_data_name: String,
+    pub _remaining_time: f32,
+}
+
 pub struct WeaponManager {
     pub _game_client: *const GameClient,
-    pub _id_generator: u64,
-    pub _bullets_array: HashMap<u64, Rc<Bullet>>,
+    pub _bullets: Vec<Bullet>,
+    pub _free_bullet_handles: HashMap<String, Vec<BulletHandle>>,
+    pub _active_bullet_handles: Vec<BulletHandle>,
+    pub _free_beam_render_object_handles: HashMap<String, Vec<usize>>,
+    pub _active_beams: Vec<HitscanBeam>,
 }
 
 impl WeaponManager {
     pub fn create_weapon_manager() -> Box<WeaponManager> {
         Box::new(WeaponManager {
             _game_client: std::ptr::null(),
-            _id_generator: 0,
-            _bullets_array: HashMap::new(),
+            _bullets: Vec::with_capacity(MAX_BULLET_COUNT),
+            _free_bullet_handles: HashMap::new(),
+            _active_bullet_handles: Vec::with_capacity(MAX_BULLET_COUNT),
+            _free_beam_render_object_handles: HashMap::new(),
+            _active_beams: Vec::new(),
         })
     }
     pub fn initialize_weapon_manager(&mut self, game_client: &GameClient) {
         self._game_client = game_client;
     }
     pub fn destroy_weapon_manager(&mut self) {
-        self._bullets_array.clear();
+        self._active_beams.clear();
+        self._free_beam_render_object_handles.clear();
+        self._active_bullet_handles.clear();
+        self._free_bullet_handles.clear();
+        self._bullets.clear();
     }
     pub fn get_game_client(&self) -> &GameClient { ptr_as_ref(self._game_client) }
     pub fn get_game_client_mut(&self) -> &mut GameClient { ptr_as_mut(self._game_client) }
-    pub fn generate_id(&mut self) -> u64 {
-        let id = self._id_generator;
-        self._id_generator += 1;
-        id
-    }
-    pub fn regist_bullets(&mut self, bullet: &Rc<Bullet>) -> u64 {
-        let id = self.generate_id();
-        self._bullets_array.insert(id, bullet.clone());
-        id
+    pub fn get_bullet(&self, bullet_handle: BulletHandle) -> &Bullet { &self._bullets[bullet_handle] }
+
+    // the bullet slots and their render objects are allocated in chunks per model and reused after that
+    fn preallocate_bullets(&mut self, model_data_name: &str) {
+        let project_scene_manager = ptr_as_ref(self._game_client).get_project_scene_manager_mut();
+        let render_object_create_info = RenderObjectCreateInfo {
+            _model_data_name: model_data_name.to_string(),
+            ..Default::default()
+        };
+        let mut bullet_handles: Vec<BulletHandle> = Vec::with_capacity(BULLET_POOL_ALLOCATION_COUNT);
+        for _ in 0..BULLET_POOL_ALLOCATION_COUNT {
+            let render_object_handle = project_scene_manager.add_pooled_render_object("bullet", &render_object_create_info);
+            let bullet_render_object = project_scene_manager.get_pooled_render_object(render_object_handle);
+            bullet_handles.push(self._bullets.len());
+            self._bullets.push(Bullet::create_bullet(render_object_handle, bullet_render_object));
+        }
+        self._free_bullet_handles.entry(model_data_name.to_string()).or_insert_with(Vec::new).append(&mut bullet_handles);
     }
-    pub fn unregist_bullets(&mut self, id: u64) {
-        self._bullets_array.remove(&id);
+
+    // none when the pool is full, the shot is dropped then
+    fn pop_free_bullet(&mut self, model_data_name: &str) -> Option<BulletHandle> {
+        if let Some(bullet_handle) = self._free_bullet_handles.get_mut(model_data_name).and_then(|bullet_handles| bullet_handles.pop()) {
+            return Some(bullet_handle);
+        }
+        if MAX_BULLET_COUNT <= self._bullets.len() {
+            return None;
+        }
+        self.preallocate_bullets(model_data_name);
+        self._free_bullet_handles.get_mut(model_data_name).unwrap().pop()
     }
-    pub fn fire_bullet(&mut self, weapon_ptr: *const dyn WeaponTrait, render_object_create_info: &RenderObjectCreateInfo) {
-        let bullet_render_object = self.get_game_client().get_project_scene_manager_mut().add_static_render_object("bullet", render_object_create_info);
-        self.get_game_client().get_audio_manager_mut().create_audio_instance("assaultrifle1", AudioLoop::ONCE);
+
+    pub fn fire_bullet(&mut self, weapon_ptr: *const dyn WeaponTrait, position: &Vector3<f32>, rotation: &Vector3<f32>) {
+        let game_client = ptr_as_ref(self._game_client);
         let weapon = ptr_as_ref(weapon_ptr);
-        let bullet = Bullet::create_bullet(
-            weapon.get_owner_actor(),
-            weapon.get_owner_actor().get_velocity(),
-            weapon.get_bullet_data(),
-            &bullet_render_object
+        let owner_actor_handle = weapon.get_owner_actor_handle();
+        let owner_actor = match game_client.get_actor_manager().get_actor(owner_actor_handle) {
+            Some(owner_actor) => owner_actor,
+            None => return,
+        };
+        let bullet_data = weapon.get_bullet_data();
+        let bullet_handle = match self.pop_free_bullet(&bullet_data._model_data_name) {
+            Some(bullet_handle) => bullet_handle,
+            None => return,
+        };
+        let bullet = &mut self._bullets[bullet_handle];
+        bullet.initialize_bullet(
+            owner_actor_handle,
+            owner_actor.get_faction_id(),
+            owner_actor.get_velocity(),
+            bullet_data,
+            position,
+            rotation
         );
-        self.regist_bullets(&bullet);
+        game_client.get_project_scene_manager_mut().set_pooled_render_object_visible(bullet._render_object_handle, true);
+        self._active_bullet_handles.push(bullet_handle);
+    }
+
+    pub fn fire_hitscan(&mut self, weapon_ptr: *const dyn WeaponTrait, start_position: &Vector3<f32>, direction: &Vector3<f32>) -> Option<Vector3<f32>> {
+        let game_client = ptr_as_ref(self._game_client);
+        let weapon = ptr_as_ref(weapon_ptr);
+        let bullet_data = weapon.get_bullet_data();
+        let owner_actor_handle = weapon.get_owner_actor_handle();
+        let owner_faction_id = match game_client.get_actor_manager().get_actor(owner_actor_handle) {
+            Some(owner_actor) => owner_actor.get_faction_id(),
+            None => return None,
+        };
+
+        game_client.get_game_event_manager_mut().publish_event(GameEvent::WeaponFired {
+            _owner_actor_handle: owner_actor_handle,
+            _position: start_position.clone_owned(),
+        });
+
+        // find the nearest actor or the scene along the ray
+        let end_position = start_position + direction * bullet_data._bullet_range;
+        let mut candidate_actor_handles: Vec<ActorHandle> = Vec::new();
+        let (hit_actor_handle, collision_hit) = WeaponManager::check_collision(game_client, owner_faction_id, start_position, &end_position, &mut candidate_actor_handles);
+
+        let beam_end_position = match collision_hit.as_ref() {
+            Some(collision_hit) => &collision_hit._hit_position,
+            None => &end_position,
+        };
+        let rate_of_fire = weapon.get_weapon_data()._rate_of_fire;
+        let beam_time = if 0.0 < rate_of_fire { HITSCAN_BEAM_MIN_TIME.max(1.0 / rate_of_fire) } else { HITSCAN_BEAM_MIN_TIME };
+        self.show_hitscan_beam(&bullet_data._model_data_name, start_position, beam_end_position, beam_time);
+
+        match collision_hit {
+            Some(collision_hit) => {
+                if let Some(hit_actor_handle) = hit_actor_handle {
+                    WeaponManager::apply_bullet_damage(game_client, bullet_data, owner_actor_handle, hit_actor_handle);
+                }
+                WeaponManager::spawn_bullet_destroy_effect(game_client, bullet_data, &collision_hit._hit_position, &collision_hit.get_hit_rotation());
+                Some(collision_hit._hit_position)
+            },
+            None => None
+        }
+    }
+
+    // the bullet model faces -front like a flying bullet and is one unit long, so the z scale is the beam length
+    fn show_hitscan_beam(&mut self, model_data_name: &str, start_position: &Vector3<f32>, end_position: &Vector3<f32>, beam_time: f32) {
+        let beam_vector = end_position - start_position;
+        let beam_length = beam_vector.norm();
+        if beam_length <= 0.0 {
+            return;
+        }
+
+        let project_scene_manager = ptr_as_ref(self._game_client).get_project_scene_manager_mut();
+        let render_object_handle = match self._free_beam_render_object_handles.get_mut(model_data_name).and_then(|render_object_handles| render_object_handles.pop()) {
+            Some(render_object_handle) => render_object_handle,
+            None => {
+                let render_object_create_info = RenderObjectCreateInfo {
+                    _model_data_name: model_data_name.to_string(),
+                    ..Default::default()
+                };
+                project_scene_manager.add_pooled_render_object("beam", &render_object_create_info)
+            }
+        };
+
+        let beam_direction = beam_vector / beam_length;
+        let beam_rotation = Vector3::new(beam_direction.y.asin(), (-beam_direction.x).atan2(-beam_direction.z), 0.0);
+        {
+            let mut beam_render_object = project_scene_manager.get_pooled_render_object(render_object_handle).borrow_mut();
+            beam_render_object._transform_object.set_position(start_position);
+            beam_render_object._transform_object.set_rotation(&beam_rotation);
+            beam_render_object._transform_object.set_scale(&Vector3::new(1.0, 1.0, beam_length));
+        }
+        project_scene_manager.set_pooled_render_object_visible(render_object_handle, true);
+        self._active_beams.push(HitscanBeam {
+            _render_object_handle: render_object_handle,
+            _model_data_name: model_data_name.to_string(),
+            _remaining_time: beam_time,
+        });
+    }
+
+    // returns the nearest hit, the actor is none when the sea or a static object of the scene is hit first
+    pub fn check_collision(
+        game_client: &GameClient,
+        owner_faction_id: FactionId,
+        start_position: &Vector3<f32>,
+        end_position: &Vector3<f32>,
+        candidate_actor_handles: &mut Vec<ActorHandle>
+    ) -> (Option<ActorHandle>, Option<CollisionHit>) {
+        let actor_manager = game_client.get_actor_manager_mut();
+        let project_scene_manager = game_client.get_project_scene_manager();
+
+        // broadphase
+        actor_manager.get_spatial_grid_mut().query_segment(start_position, end_position, candidate_actor_handles);
+
+        let mut hit_actor_handle: Option<ActorHandle> = None;
+        let mut collision_hit: Option<CollisionHit> = None;
+        for actor_handle in candidate_actor_handles.iter() {
+            if let Some(actor) = actor_manager.get_actor(*actor_handle) {
+                if false == actor_manager.is_allied_faction(owner_faction_id, actor) && false == actor.get_ship().is_destroyed() {
+                    if let Some(hit) = intersect_segment_bound_box(start_position, end_position, actor.get_bound_box()) {
+                        let is_nearest = match collision_hit.as_ref() {
+                            Some(nearest_hit) => hit._hit_ratio < nearest_hit._hit_ratio,
+                            None => true,
+                        };
+                        if is_nearest {
+                            hit_actor_handle = Some(*actor_handle);
+                            collision_hit = Some(hit);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut scene_hit = intersect_segment_height_plane(start_position, end_position, project_scene_manager.get_sea_height());
+        for static_object in project_scene_manager.get_static_collision_objects().iter() {
+            // the bounds of each geometry such as the floor and the walls, ships fly inside of the bounds of the whole stage.
+            // only entering the bounds is a hit, so the segment which starts inside of a geometry bound is not consumed at once
+            for geometry_bound_box in static_object.borrow()._geometry_bound_boxes.iter() {
+                let hit = intersect_segment_bound_box(start_position, end_position, geometry_bound_box)
+                    .filter(|hit| 0.0 < hit._hit_ratio);
+                scene_hit = get_nearest_collision_hit(scene_hit, hit);
+            }
+        }
+
+        if let Some(scene_hit) = scene_hit {
+            let is_scene_nearer = match collision_hit.as_ref() {
+                Some(actor_hit) => scene_hit._hit_ratio < actor_hit._hit_ratio,
+                None => true,
+            };
+            if is_scene_nearer {
+                return (None, Some(scene_hit));
+            }
+        }
+        (hit_actor_handle, collision_hit)
+    }
+
+    // the owner may be removed already, the events refer to its handle anyway.
+    // the killed actor starts the destruction on its next update and is removed by the actor manager.
+    pub fn apply_bullet_damage(game_client: &GameClient, bullet_data: &BulletData, owner_actor_handle: ActorHandle, hit_actor_handle: ActorHandle) -> Option<ShipDamageResult> {
+        let actor = ptr_as_mut(game_client.get_actor_manager().get_actor(hit_actor_handle)?);
+        let damage_result = actor.get_ship_mut().apply_damage(bullet_data._shield_damage, bullet_data._hull_damage);
+        if let Some(damage_result) = damage_result.as_ref() {
+            let game_event_manager = game_client.get_game_event_manager_mut();
+            game_event_manager.publish_event(GameEvent::ActorDamaged {
+                _actor_handle: hit_actor_handle,
+                _attacker_actor_handle: owner_actor_handle,
+                _damage_result: *damage_result,
+            });
+            if ShipDamageType::Killed == damage_result._damage_type {
+                game_event_manager.publish_event(GameEvent::ActorDestroyed {
+                    _actor_handle: hit_actor_handle,
+                    _attacker_actor_handle: owner_actor_handle,
+                    _position: actor.get_transform().get_position().clone_owned(),
+                });
+            }
+        }
+        damage_result
+    }
+
+    pub fn spawn_bullet_destroy_effect(game_client: &GameClient, bullet_data: &BulletData, position: &Vector3<f32>, rotation: &Vector3<f32>) {
+        let bullet_destroy_effect_count = bullet_data._bullet_destroy_effects.len();
+        if 0 < bullet_destroy_effect_count {
+            let effect_index: usize = if 1 < bullet_destroy_effect_count { rand::random::<usize>() % bullet_destroy_effect_count } else { 0 };
+            let effect_create_info = EffectCreateInfo {
+                _effect_position: position.clone_owned(),
+                _effect_rotation: rotation.clone_owned(),
+                _effect_data_name: bullet_data._bullet_destroy_effects[effect_index].clone(),
+                ..Default::default()
+            };
+            game_client.get_project_scene_manager_mut().add_effect(&effect_create_info._effect_data_name, &effect_create_info);
+        }
+
+        if false == bullet_data._bullet_destroy_sound_bank.is_empty() {
+            game_client.get_audio_manager_mut().create_audio_instance_from_bank(&bullet_data._bullet_destroy_sound_bank, AudioLoop::ONCE);
+        }
     }
 
     pub fn update_weapon_manager(&mut self, delta_time: f32) {
         let game_client = ptr_as_ref(self._game_client);
         let project_scene_manager = game_client.get_project_scene_manager_mut();
-        let audio_manager = game_client.get_audio_manager_mut();
-        let actor_manager = game_client.get_actor_manager_mut();
 
         // update bullet
-        let mut dead_bullets: Vec<(u64, *const Bullet)> = Vec::new();
-        for (id, bullet_ptr) in self._bullets_array.iter() {
-            let bullet = ptr_as_mut(bullet_ptr.as_ref());
+        let mut candidate_actor_handles: Vec<ActorHandle> = Vec::new();
+        let mut index: usize = 0;
+        while index < self._active_bullet_handles.len() {
+            let bullet_handle = self._active_bullet_handles[index];
+            let bullet = &mut self._bullets[bullet_handle];
 
             bullet.update_bullet(delta_time, project_scene_manager);
 
-            // check hit
+            // check hit along the moved segment, so fast bullets do not pass through small ships
             if bullet._is_alive {
-                let bullet_position = bullet.get_transform_object().get_position();
-                let is_player_actor = bullet.get_owner_actor().is_player_actor();
-                for actor_wrapper in actor_manager._actors.values() {
-                    let actor = ptr_as_mut(actor_wrapper.as_ref());
-                    if is_player_actor != actor.is_player_actor() {
-                        let is_hit = {
-                            let actor_bound_box = actor.get_bound_box();
-                            let to_actor = &actor_bound_box._center - bullet_position;
-                            to_actor.norm_squared() <= (actor_bound_box._radius * actor_bound_box._radius)
-                        };
+                let (hit_actor_handle, collision_hit) = WeaponManager::check_collision(
+                    game_client,
+                    bullet.get_owner_faction_id(),
+                    bullet.get_prev_position(),
+                    bullet.get_transform_object().get_position(),
+                    &mut candidate_actor_handles
+                );
 
-                        if is_hit {
-                            actor_manager.remove_actor(project_scene_manager, actor);
-                            bullet._is_alive = false;
-                            bullet._is_collided = true;
-                            break;
-                        }
+                if let Some(collision_hit) = collision_hit {
+                    if let Some(hit_actor_handle) = hit_actor_handle {
+                        WeaponManager::apply_bullet_damage(game_client, bullet.get_bullet_data(), bullet.get_owner_actor_handle(), hit_actor_handle);
                     }
+                    bullet.set_collision_hit(collision_hit);
                 }
             }
 
-            if false == bullet._is_alive {
-                dead_bullets.push((*id, bullet_ptr.as_ref()));
+            if bullet.update_bullet_expiration() {
+                index += 1;
+                continue;
             }
-        }
 
-        // destroy bullets
-        for (id, bullet_ptr) in dead_bullets.iter() {
-            let bullet = ptr_as_mut(*bullet_ptr);
-            if bullet._is_collided {
-                let bullet_transform = bullet.get_transform_object();
-                let bullet_data = bullet.get_bullet_data();
-                let bullet_destroy_effect_count = bullet_data._bullet_destroy_effects.len();
-                if 0 < bullet_destroy_effect_count {
-                    let effect_index: usize = if 1 < bullet_destroy_effect_count { rand::random::<usize>() % bullet_destroy_effect_count } else { 0 };
-                    let effect_create_info = EffectCreateInfo {
-                        _effect_position: bullet_transform.get_position().clone_owned(),
-                        _effect_rotation: bullet_transform.get_rotation().clone_owned(),
-                        _effect_data_name: bullet_data._bullet_destroy_effects[effect_index].clone(),
-                        ..Default::default()
-                    };
-                    project_scene_manager.add_effect(&effect_create_info._effect_data_name, &effect_create_info);
-                }
+            // return the dead bullet to the pool
+            if let Some(collision_hit) = bullet._collision_hit.as_ref() {
+                WeaponManager::spawn_bullet_destroy_effect(game_client, bullet.get_bullet_data(), &collision_hit._hit_position, &collision_hit.get_hit_rotation());
+            }
+            project_scene_manager.set_pooled_render_object_visible(bullet._render_object_handle, false);
+            if let Some(bullet_handles) = self._free_bullet_handles.get_mut(&bullet.get_bullet_data()._model_data_name) {
+                bullet_handles.push(bullet_handle);
+            }
+            self._active_bullet_handles.swap_remove(index);
+        }
 
-                if false == bullet_data._bullet_destroy_sound_bank.is_empty() {
-                    audio_manager.create_audio_instance_from_bank(&bullet_data._bullet_destroy_sound_bank, AudioLoop::ONCE);
-                }
+        // update hitscan beams
+        let mut index: usize = 0;
+        while index < self._active_beams.len() {
+            let beam = &mut self._active_beams[index];
+            beam._remaining_time -= delta_time;
+            if 0.0 < beam._remaining_time {
+                index += 1;
+                continue;
             }
-            project_scene_manager.remove_static_render_object(&bullet._bullet_render_object.borrow()._render_object_name);
 
-            self.unregist_bullets(*id);
+            project_scene_manager.set_pooled_render_object_visible(beam._render_object_handle, false);
+            self._free_beam_render_object_handles.entry(beam._model_data_name.clone()).or_insert_with(Vec::new).push(beam._render_object_handle);
+            self._active_beams.swap_remove(index);
         }
     }
 
@@ -151,4 +375,4 @@ impl WeaponManager {
             bullet.update_bullet(self.core_manager.debug_line_manager, delta_time)
     }
 */
-}
\ No newline at end of file
+}
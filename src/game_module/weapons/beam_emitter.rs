@@ -0,0 +1,41 @@
+use rust_engine_3d::renderer::transform_object::TransformObjectData;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::weapons::weapon::{WeaponTrait, WeaponBase};
+
+pub struct BeamEmitter {
+    pub _weapon_base: WeaponBase,
+}
+
+// Implementation
+impl BeamEmitter {
+    pub fn create_beam_emitter(weapon_base: WeaponBase) -> Box<BeamEmitter> {
+        Box::new(BeamEmitter {
+            _weapon_base: weapon_base,
+        })
+    }
+}
+
+impl WeaponTrait for BeamEmitter {
+    fn initialize_weapon(&mut self) {
+    }
+    fn get_weapon_base(&self) -> &WeaponBase { &self._weapon_base }
+    fn get_weapon_base_mut(&mut self) -> &mut WeaponBase { &mut self._weapon_base }
+    fn weapon_fire(&mut self, game_client: &GameClient) {
+        self._weapon_base.request_fire();
+        if false == self._weapon_base.is_fire_ready() {
+            return;
+        }
+
+        let weapon_ptr: *const dyn WeaponTrait = self;
+        let weapon_data = self.get_weapon_data();
+        let rate_of_fire = weapon_data._rate_of_fire;
+        let bullet_amount = weapon_data._bullet_amount;
+        let bullet_spread = weapon_data._bullet_spread;
+        self._weapon_base.fire_bullets(weapon_ptr, game_client, bullet_amount, bullet_spread);
+        self._weapon_base.play_fire_sound(game_client);
+        self._weapon_base.set_fire_cooldown(rate_of_fire);
+    }
+    fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32) {
+        self._weapon_base.update_weapon_base(ship_transform_object, delta_time);
+    }
+}
@@ -1,5 +1,3 @@
-use std::rc::Rc;
-
 use nalgebra::Vector3;
 use serde::{ Serialize, Deserialize };
 
@@ -7,7 +5,9 @@ use rust_engine_3d::renderer::render_object::RenderObjectData;
 use rust_engine_3d::renderer::transform_object::TransformObjectData;
 use rust_engine_3d::utilities::system::RcRefCell;
 use crate::application::project_scene_manager::ProjectSceneManager;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actor_manager::ActorHandle;
+use crate::game_module::collision::CollisionHit;
+use crate::game_module::faction::FactionId;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Copy)]
 pub enum BulletType {
@@ -58,13 +58,18 @@ impl Default for BulletData {
 
 pub struct Bullet {
     pub _bullet_data: *const BulletData,
-    pub _owner_actor: *const ActorController,
+    pub _owner_actor_handle: ActorHandle,
+    // kept to filter the allies after the owner is removed
+    pub _owner_faction_id: FactionId,
     pub _is_alive: bool,
     pub _is_collided: bool,
     pub _elapsed_time: f32,
     pub _transform: *const TransformObjectData,
     pub _initial_position: Vector3<f32>,
     pub _initial_velocity: Vector3<f32>,
+    pub _prev_position: Vector3<f32>,
+    pub _collision_hit: Option<CollisionHit>,
+    pub _render_object_handle: usize,
     pub _bullet_render_object: RcRefCell<RenderObjectData>,
 }
 
@@ -72,59 +77,85 @@ pub struct Bullet {
 // Implementation
 impl Bullet {
     pub fn create_bullet(
-        owner_actor: *const ActorController,
-        initial_velocity: &Vector3<f32>,
-        bullet_data: *const BulletData,
+        render_object_handle: usize,
         bullet_render_object: &RcRefCell<RenderObjectData>,
-    ) -> Rc<Bullet> {
-        Rc::new(Bullet {
-            _owner_actor: owner_actor,
+    ) -> Bullet {
+        Bullet {
+            _owner_actor_handle: ActorHandle::default(),
+            _owner_faction_id: 0,
             _transform: &bullet_render_object.borrow()._transform_object,
-            _initial_position: bullet_render_object.borrow()._transform_object.get_position().clone_owned(),
-            _initial_velocity: initial_velocity.clone_owned(),
-            _bullet_data: bullet_data,
+            _initial_position: Vector3::zeros(),
+            _initial_velocity: Vector3::zeros(),
+            _prev_position: Vector3::zeros(),
+            _collision_hit: None,
+            _bullet_data: std::ptr::null(),
             _elapsed_time: 0.0,
-            _is_alive: true,
+            _is_alive: false,
             _is_collided: false,
+            _render_object_handle: render_object_handle,
             _bullet_render_object: bullet_render_object.clone(),
-        })
+        }
     }
-    pub fn get_owner_actor(&self) -> &ActorController {
-        unsafe { &*self._owner_actor }
+    pub fn initialize_bullet(
+        &mut self,
+        owner_actor_handle: ActorHandle,
+        owner_faction_id: FactionId,
+        initial_velocity: &Vector3<f32>,
+        bullet_data: *const BulletData,
+        position: &Vector3<f32>,
+        rotation: &Vector3<f32>,
+    ) {
+        let transform = self.get_transform_object_mut();
+        transform.set_position(position);
+        transform.set_rotation(rotation);
+        transform.update_transform_object();
+
+        self._owner_actor_handle = owner_actor_handle;
+        self._owner_faction_id = owner_faction_id;
+        self._bullet_data = bullet_data;
+        self._initial_position.clone_from(position);
+        self._initial_velocity.clone_from(initial_velocity);
+        self._prev_position.clone_from(position);
+        self._collision_hit = None;
+        self._elapsed_time = 0.0;
+        self._is_alive = true;
+        self._is_collided = false;
     }
-    pub fn get_owner_actor_mut(&self) -> &mut ActorController { unsafe { &mut *(self._owner_actor as *mut ActorController) } }
+    pub fn get_owner_actor_handle(&self) -> ActorHandle { self._owner_actor_handle }
+    pub fn get_owner_faction_id(&self) -> FactionId { self._owner_faction_id }
     pub fn get_bullet_type(&self) -> BulletType { self.get_bullet_data()._bullet_type }
     pub fn get_bullet_data(&self) -> &BulletData { unsafe { &*self._bullet_data } }
     pub fn get_transform_object(&self) -> &TransformObjectData { unsafe { &*self._transform } }
     pub fn get_transform_object_mut(&self) -> &mut TransformObjectData { unsafe { &mut *(self._transform as *mut TransformObjectData) } }
+    pub fn get_prev_position(&self) -> &Vector3<f32> { &self._prev_position }
+    pub fn set_collision_hit(&mut self, collision_hit: CollisionHit) {
+        self._is_alive = false;
+        self._is_collided = true;
+        self._collision_hit = Some(collision_hit);
+    }
     pub fn update_bullet(&mut self, delta_time: f32, _project_scene_manager: &ProjectSceneManager) -> bool {
         if self._is_alive {
             let bullet_data = unsafe { &*self._bullet_data };
 
             // move bullet
             let transform = unsafe { &mut *(self._transform as *mut TransformObjectData) };
+            self._prev_position.clone_from(transform.get_position());
             let velocity = (&self._initial_velocity - transform.get_front() * bullet_data._bullet_speed) * delta_time;
             transform.move_position(&velocity);
 
-            let current_position = transform.get_position();
-            // check bullet range
-            if self._is_alive {
-                let move_distance = (current_position - &self._initial_position).norm();
-                if bullet_data._bullet_life_time < self._elapsed_time || bullet_data._bullet_range < move_distance {
-                    self._is_alive = false;
-                }
-            }
-
-            // todo: check bullet collision
-            // if self._is_alive {
-            //     if current_position.y < floating_height {
-            //         self._is_alive = false;
-            //         self._is_collided = true;
-            //     }
-            // }
-
             self._elapsed_time += delta_time;
         }
         self._is_alive
     }
+    // called after the hit test of the moved segment, so the last segment before the expiration still hits
+    pub fn update_bullet_expiration(&mut self) -> bool {
+        if self._is_alive {
+            let bullet_data = unsafe { &*self._bullet_data };
+            let move_distance = (self.get_transform_object().get_position() - &self._initial_position).norm();
+            if bullet_data._bullet_life_time < self._elapsed_time || bullet_data._bullet_range < move_distance {
+                self._is_alive = false;
+            }
+        }
+        self._is_alive
+    }
 }
\ No newline at end of file
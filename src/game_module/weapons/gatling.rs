@@ -0,0 +1,59 @@
+use rust_engine_3d::renderer::transform_object::TransformObjectData;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::game_constants::GATLING_MIN_FIRE_RATE_RATIO;
+use crate::game_module::weapons::weapon::{WeaponTrait, WeaponBase};
+
+// rate of fire ramps up while the trigger is held
+pub struct Gatling {
+    pub _weapon_base: WeaponBase,
+    pub _spin_ratio: f32,
+}
+
+// Implementation
+impl Gatling {
+    pub fn create_gatling(weapon_base: WeaponBase) -> Box<Gatling> {
+        Box::new(Gatling {
+            _weapon_base: weapon_base,
+            _spin_ratio: 0.0,
+        })
+    }
+    pub fn get_spin_ratio(&self) -> f32 {
+        self._spin_ratio
+    }
+}
+
+impl WeaponTrait for Gatling {
+    fn initialize_weapon(&mut self) {
+        self._spin_ratio = 0.0;
+    }
+    fn get_weapon_base(&self) -> &WeaponBase { &self._weapon_base }
+    fn get_weapon_base_mut(&mut self) -> &mut WeaponBase { &mut self._weapon_base }
+    fn weapon_fire(&mut self, game_client: &GameClient) {
+        self._weapon_base.request_fire();
+        if false == self._weapon_base.is_fire_ready() {
+            return;
+        }
+
+        let weapon_ptr: *const dyn WeaponTrait = self;
+        let weapon_data = self.get_weapon_data();
+        let rate_of_fire = weapon_data._rate_of_fire * GATLING_MIN_FIRE_RATE_RATIO.max(self._spin_ratio);
+        let bullet_amount = weapon_data._bullet_amount;
+        let bullet_spread = weapon_data._bullet_spread;
+        self._weapon_base.fire_bullets(weapon_ptr, game_client, bullet_amount, bullet_spread);
+        self._weapon_base.play_fire_sound(game_client);
+        self._weapon_base.set_fire_cooldown(rate_of_fire);
+    }
+    fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32) {
+        self._weapon_base.update_weapon_base(ship_transform_object, delta_time);
+
+        // spin up while firing, spin down otherwise
+        let spin_up_time = self.get_weapon_data()._spin_up_time;
+        if spin_up_time <= 0.0 {
+            self._spin_ratio = 1.0;
+        } else {
+            let spin_delta = delta_time / spin_up_time;
+            let spin_ratio = if self._weapon_base.is_firing() { self._spin_ratio + spin_delta } else { self._spin_ratio - spin_delta };
+            self._spin_ratio = 0f32.max(1f32.min(spin_ratio));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use rust_engine_3d::renderer::transform_object::TransformObjectData;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::weapons::weapon::{WeaponTrait, WeaponBase};
+
+// continuous hitscan beam, damage is applied on every fire tick
+pub struct LaserEmitter {
+    pub _weapon_base: WeaponBase,
+}
+
+// Implementation
+impl LaserEmitter {
+    pub fn create_laser_emitter(weapon_base: WeaponBase) -> Box<LaserEmitter> {
+        Box::new(LaserEmitter {
+            _weapon_base: weapon_base,
+        })
+    }
+}
+
+impl WeaponTrait for LaserEmitter {
+    fn initialize_weapon(&mut self) {
+    }
+    fn get_weapon_base(&self) -> &WeaponBase { &self._weapon_base }
+    fn get_weapon_base_mut(&mut self) -> &mut WeaponBase { &mut self._weapon_base }
+    fn weapon_fire(&mut self, game_client: &GameClient) {
+        // play the sound only when the beam turns on
+        if false == self._weapon_base.is_firing() {
+            self._weapon_base.play_fire_sound(game_client);
+        }
+
+        self._weapon_base.request_fire();
+        if false == self._weapon_base.is_fire_ready() {
+            return;
+        }
+
+        let weapon_ptr: *const dyn WeaponTrait = self;
+        let rate_of_fire = self.get_weapon_data()._rate_of_fire;
+        let muzzle_position = self._weapon_base.get_muzzle_position().clone_owned();
+        let muzzle_direction = self._weapon_base.get_muzzle_direction().normalize();
+        game_client.get_weapon_manager_mut().fire_hitscan(weapon_ptr, &muzzle_position, &muzzle_direction);
+        self._weapon_base.set_fire_cooldown(rate_of_fire);
+    }
+    fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32) {
+        self._weapon_base.update_weapon_base(ship_transform_object, delta_time);
+    }
+}
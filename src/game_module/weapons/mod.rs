@@ -1,2 +1,7 @@
+pub mod beam_emitter;
 pub mod bullet;
-pub mod weapon;
\ No newline at end of file
+pub mod gatling;
+pub mod laser_emitter;
+pub mod plasma_emitter;
+pub mod shotgun;
+pub mod weapon;
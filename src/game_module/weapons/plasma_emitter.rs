@@ -0,0 +1,57 @@
+use rust_engine_3d::renderer::transform_object::TransformObjectData;
+use crate::game_module::game_client::GameClient;
+use crate::game_module::weapons::weapon::{WeaponTrait, WeaponBase};
+
+// charges while the trigger is held and fires once the charge is full
+pub struct PlasmaEmitter {
+    pub _weapon_base: WeaponBase,
+    pub _charge_elapsed_time: f32,
+}
+
+// Implementation
+impl PlasmaEmitter {
+    pub fn create_plasma_emitter(weapon_base: WeaponBase) -> Box<PlasmaEmitter> {
+        Box::new(PlasmaEmitter {
+            _weapon_base: weapon_base,
+            _charge_elapsed_time: 0.0,
+        })
+    }
+    pub fn get_charge_ratio(&self) -> f32 {
+        let charge_time = self.get_weapon_data()._charge_time;
+        if charge_time <= 0.0 { 1.0 } else { 1f32.min(self._charge_elapsed_time / charge_time) }
+    }
+}
+
+impl WeaponTrait for PlasmaEmitter {
+    fn initialize_weapon(&mut self) {
+        self._charge_elapsed_time = 0.0;
+    }
+    fn get_weapon_base(&self) -> &WeaponBase { &self._weapon_base }
+    fn get_weapon_base_mut(&mut self) -> &mut WeaponBase { &mut self._weapon_base }
+    fn weapon_fire(&mut self, game_client: &GameClient) {
+        self._weapon_base.request_fire();
+        if false == self._weapon_base.is_fire_ready() || self.get_charge_ratio() < 1.0 {
+            return;
+        }
+
+        let weapon_ptr: *const dyn WeaponTrait = self;
+        let weapon_data = self.get_weapon_data();
+        let rate_of_fire = weapon_data._rate_of_fire;
+        let bullet_amount = weapon_data._bullet_amount;
+        let bullet_spread = weapon_data._bullet_spread;
+        self._weapon_base.fire_bullets(weapon_ptr, game_client, bullet_amount, bullet_spread);
+        self._weapon_base.play_fire_sound(game_client);
+        self._weapon_base.set_fire_cooldown(rate_of_fire);
+        self._charge_elapsed_time = 0.0;
+    }
+    fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32) {
+        self._weapon_base.update_weapon_base(ship_transform_object, delta_time);
+
+        // releasing the trigger drops the charge
+        if self._weapon_base.is_firing() {
+            self._charge_elapsed_time += delta_time;
+        } else {
+            self._charge_elapsed_time = 0.0;
+        }
+    }
+}
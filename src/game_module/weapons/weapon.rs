@@ -1,14 +1,22 @@
 use nalgebra::Vector3;
 use serde::{ Serialize, Deserialize };
 
-use rust_engine_3d::renderer::render_object::{RenderObjectData, RenderObjectCreateInfo};
+use rust_engine_3d::application::audio_manager::AudioLoop;
+use rust_engine_3d::renderer::render_object::RenderObjectData;
 use rust_engine_3d::renderer::transform_object::TransformObjectData;
-use rust_engine_3d::utilities::system::{RcRefCell, newRcRefCell};
+use rust_engine_3d::utilities::math::TWO_PI;
+use rust_engine_3d::utilities::system::{RcRefCell, newRcRefCell, ptr_as_ref};
 use crate::application::project_scene_manager::ProjectSceneManager;
-use crate::game_module::actors::actor::ActorController;
+use crate::game_module::actor_manager::ActorHandle;
 use crate::game_module::game_client::GameClient;
 use crate::game_module::game_constants::{FIRE_PITCH_MIN, FIRE_PITCH_MAX};
+use crate::game_module::game_event_manager::GameEvent;
+use crate::game_module::weapons::beam_emitter::BeamEmitter;
 use crate::game_module::weapons::bullet::{BulletType, BulletData};
+use crate::game_module::weapons::gatling::Gatling;
+use crate::game_module::weapons::laser_emitter::LaserEmitter;
+use crate::game_module::weapons::plasma_emitter::PlasmaEmitter;
+use crate::game_module::weapons::shotgun::Shotgun;
 
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug, Copy)]
@@ -31,6 +39,8 @@ pub const WEAPON_TYPES: [WeaponType; 5] = [
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct WeaponSlotData {
+    pub _weapon_data_name: String,
+    pub _fire_group: usize,
     pub _position: Vector3<f32>,
     pub _rotation: Vector3<f32>,
     pub _scale: Vector3<f32>,
@@ -39,6 +49,8 @@ pub struct WeaponSlotData {
 impl Default for WeaponSlotData {
     fn default() -> WeaponSlotData {
         WeaponSlotData {
+            _weapon_data_name: "".to_string(),
+            _fire_group: 0,
             _position: Vector3::zeros(),
             _rotation: Vector3::zeros(),
             _scale: Vector3::new(1.0, 1.0, 1.0),
@@ -52,6 +64,10 @@ pub struct WeaponDataCreateInfo {
     pub _weapon_type: WeaponType,
     pub _rate_of_fire: f32,
     pub _bullet_amount: i32,
+    pub _bullet_spread: f32,
+    pub _spin_up_time: f32,
+    pub _charge_time: f32,
+    pub _fire_sound_name: String,
     pub _bullet_data_name: String,
     pub _model_data_name: String,
     pub _muzzle_position: Vector3<f32>,
@@ -63,6 +79,10 @@ impl Default for WeaponDataCreateInfo {
             _weapon_type: WeaponType::BeamEmitter,
             _rate_of_fire: 1.0,
             _bullet_amount: 1,
+            _bullet_spread: 0.0,
+            _spin_up_time: 0.0,
+            _charge_time: 0.0,
+            _fire_sound_name: "".to_string(),
             _bullet_data_name: "".to_string(),
             _model_data_name: "".to_string(),
             _muzzle_position: Vector3::zeros(),
@@ -76,6 +96,10 @@ pub struct WeaponData {
     pub _weapon_type: WeaponType,
     pub _rate_of_fire: f32,
     pub _bullet_amount: i32,
+    pub _bullet_spread: f32,
+    pub _spin_up_time: f32,
+    pub _charge_time: f32,
+    pub _fire_sound_name: String,
     pub _bullet_data: RcRefCell<BulletData>,
     pub _model_data_name: String,
     pub _muzzle_position: Vector3<f32>,
@@ -83,23 +107,32 @@ pub struct WeaponData {
 
 pub trait WeaponTrait {
     fn initialize_weapon(&mut self);
-    fn remove_weapon(&mut self, project_scene_manager: &mut ProjectSceneManager);
-    fn get_owner_actor(&self) -> &ActorController;
-    fn get_bullet_type(&self) -> BulletType;
-    fn get_bullet_data(&self) -> &BulletData;
-    fn get_weapon_type(&self) -> WeaponType;
-    fn get_weapon_data(&self) -> &WeaponData;
-    fn get_weapon_render_object(&self) -> &RcRefCell<RenderObjectData>;
+    fn get_weapon_base(&self) -> &WeaponBase;
+    fn get_weapon_base_mut(&mut self) -> &mut WeaponBase;
+    fn remove_weapon(&mut self, project_scene_manager: &mut ProjectSceneManager) {
+        self.get_weapon_base_mut().remove_weapon_base(project_scene_manager);
+    }
+    fn get_owner_actor_handle(&self) -> ActorHandle { self.get_weapon_base().get_owner_actor_handle() }
+    fn get_bullet_type(&self) -> BulletType { self.get_bullet_data()._bullet_type }
+    fn get_bullet_data(&self) -> &BulletData { self.get_weapon_base().get_bullet_data() }
+    fn get_weapon_type(&self) -> WeaponType { self.get_weapon_data()._weapon_type }
+    fn get_weapon_data(&self) -> &WeaponData { self.get_weapon_base().get_weapon_data() }
+    fn get_weapon_render_object(&self) -> &RcRefCell<RenderObjectData> { &self.get_weapon_base()._weapon_render_object }
     fn weapon_fire(&mut self, game_client: &GameClient);
     fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32);
 }
 
-pub struct BeamEmitter {
-    pub _owner_actor: *const ActorController,
+pub struct WeaponBase {
+    pub _owner_actor_handle: ActorHandle,
     pub _weapon_data: RcRefCell<WeaponData>,
     pub _weapon_slot_transform: TransformObjectData,
     pub _transform_object: TransformObjectData,
     pub _muzzle_position: Vector3<f32>,
+    // the world point to aim at instead of the front of the weapon
+    pub _aim_position: Option<Vector3<f32>>,
+    pub _fire_cooldown: f32,
+    pub _fire_requested: bool,
+    pub _is_firing: bool,
     pub _weapon_render_object: RcRefCell<RenderObjectData>,
 }
 
@@ -111,6 +144,10 @@ impl WeaponData {
             _weapon_type: weapon_data_create_info._weapon_type,
             _rate_of_fire: weapon_data_create_info._rate_of_fire,
             _bullet_amount: weapon_data_create_info._bullet_amount,
+            _bullet_spread: weapon_data_create_info._bullet_spread,
+            _spin_up_time: weapon_data_create_info._spin_up_time,
+            _charge_time: weapon_data_create_info._charge_time,
+            _fire_sound_name: weapon_data_create_info._fire_sound_name.clone(),
             _bullet_data: bullet_data.clone(),
             _model_data_name: weapon_data_create_info._model_data_name.clone(),
             _muzzle_position: weapon_data_create_info._muzzle_position.clone_owned(),
@@ -118,50 +155,111 @@ impl WeaponData {
     }
 }
 
-impl BeamEmitter {
-    pub fn create_beam_emitter(
-        owner_actor: *const ActorController,
+pub fn create_weapon(
+    owner_actor_handle: ActorHandle,
+    weapon_data: &RcRefCell<WeaponData>,
+    weapon_slot_transform: &TransformObjectData,
+    weapon_render_object: &RcRefCell<RenderObjectData>,
+) -> Box<dyn WeaponTrait> {
+    let weapon_base = WeaponBase::create_weapon_base(owner_actor_handle, weapon_data, weapon_slot_transform, weapon_render_object);
+    let weapon_type = weapon_data.borrow()._weapon_type;
+    match weapon_type {
+        WeaponType::BeamEmitter => BeamEmitter::create_beam_emitter(weapon_base),
+        WeaponType::Gatling => Gatling::create_gatling(weapon_base),
+        WeaponType::LaserEmitter => LaserEmitter::create_laser_emitter(weapon_base),
+        WeaponType::PlasmaEmitter => PlasmaEmitter::create_plasma_emitter(weapon_base),
+        WeaponType::Shotgun => Shotgun::create_shotgun(weapon_base),
+    }
+}
+
+impl WeaponBase {
+    pub fn create_weapon_base(
+        owner_actor_handle: ActorHandle,
         weapon_data: &RcRefCell<WeaponData>,
         weapon_slot_transform: &TransformObjectData,
         weapon_render_object: &RcRefCell<RenderObjectData>,
-    ) -> Box<BeamEmitter> {
-        Box::new(BeamEmitter {
+    ) -> WeaponBase {
+        WeaponBase {
+            _owner_actor_handle: owner_actor_handle,
             _weapon_data: weapon_data.clone(),
-            _owner_actor: owner_actor,
             _weapon_slot_transform: weapon_slot_transform.clone(),
             _transform_object: TransformObjectData::new_transform_object_data(),
-            _weapon_render_object: weapon_render_object.clone(),
             _muzzle_position: Vector3::zeros(),
-        })
-    }
-}
-
-impl WeaponTrait for BeamEmitter {
-    fn initialize_weapon(&mut self) {
+            _aim_position: None,
+            _fire_cooldown: 0.0,
+            _fire_requested: false,
+            _is_firing: false,
+            _weapon_render_object: weapon_render_object.clone(),
+        }
     }
-    fn remove_weapon(&mut self, project_scene_manager: &mut ProjectSceneManager) {
+    pub fn remove_weapon_base(&mut self, project_scene_manager: &mut ProjectSceneManager) {
         project_scene_manager.remove_skeletal_render_object(&self._weapon_render_object.borrow()._render_object_name);
     }
-    fn get_owner_actor(&self) -> &ActorController { unsafe { &*self._owner_actor } }
-    fn get_bullet_type(&self) -> BulletType { self.get_bullet_data()._bullet_type }
-    fn get_bullet_data(&self) -> &BulletData { unsafe { &*self.get_weapon_data()._bullet_data.as_ptr() } }
-    fn get_weapon_type(&self) -> WeaponType { self.get_weapon_data()._weapon_type }
-    fn get_weapon_data(&self) -> &WeaponData { unsafe { &*self._weapon_data.as_ptr() } }
-    fn get_weapon_render_object(&self) -> &RcRefCell<RenderObjectData> { &self._weapon_render_object }
-    fn weapon_fire(&mut self, game_client: &GameClient) {
+    pub fn get_owner_actor_handle(&self) -> ActorHandle { self._owner_actor_handle }
+    pub fn get_weapon_data(&self) -> &WeaponData { ptr_as_ref(self._weapon_data.as_ptr()) }
+    pub fn get_bullet_data(&self) -> &BulletData { ptr_as_ref(self.get_weapon_data()._bullet_data.as_ptr()) }
+    pub fn get_muzzle_position(&self) -> &Vector3<f32> { &self._muzzle_position }
+    pub fn set_aim_position(&mut self, aim_position: Option<&Vector3<f32>>) { self._aim_position = aim_position.cloned(); }
+    pub fn get_muzzle_direction(&self) -> Vector3<f32> {
+        if let Some(aim_position) = self._aim_position.as_ref() {
+            let to_aim = aim_position - &self._muzzle_position;
+            if 0.0 < to_aim.norm_squared() {
+                return to_aim.normalize();
+            }
+        }
+        -self._transform_object.get_front()
+    }
+    pub fn get_muzzle_rotation(&self) -> Vector3<f32> {
+        if self._aim_position.is_some() {
+            let muzzle_direction = self.get_muzzle_direction();
+            let muzzle_pitch: f32 = FIRE_PITCH_MIN.max(FIRE_PITCH_MAX.min(muzzle_direction.y.asin()));
+            return Vector3::new(muzzle_pitch, (-muzzle_direction.x).atan2(-muzzle_direction.z), 0.0);
+        }
         let muzzle_front = self._transform_object.get_front();
         let muzzle_pitch: f32 = FIRE_PITCH_MIN.max(FIRE_PITCH_MAX.min( self._transform_object.get_pitch() ));
-        let rotation: Vector3<f32> = Vector3::new(muzzle_pitch, muzzle_front.x.atan2(muzzle_front.z), 0.0);
-        let render_object_create_info = RenderObjectCreateInfo {
-            _model_data_name: self.get_bullet_data()._model_data_name.clone(),
+        Vector3::new(muzzle_pitch, muzzle_front.x.atan2(muzzle_front.z), 0.0)
+    }
+    pub fn request_fire(&mut self) { self._fire_requested = true; }
+    pub fn is_firing(&self) -> bool { self._is_firing }
+    pub fn is_fire_ready(&self) -> bool { self._fire_cooldown <= 0.0 }
+    pub fn set_fire_cooldown(&mut self, rate_of_fire: f32) {
+        if 0.0 < rate_of_fire {
+            self._fire_cooldown = 1.0 / rate_of_fire;
+        }
+    }
+    pub fn play_fire_sound(&self, game_client: &GameClient) {
+        let fire_sound_name = &self.get_weapon_data()._fire_sound_name;
+        if false == fire_sound_name.is_empty() {
+            game_client.get_audio_manager_mut().create_audio_instance(fire_sound_name, AudioLoop::ONCE);
+        }
+    }
+    pub fn fire_bullets(&self, weapon: *const dyn WeaponTrait, game_client: &GameClient, bullet_amount: i32, bullet_spread: f32) {
+        let muzzle_rotation = self.get_muzzle_rotation();
+        for _ in 0..bullet_amount {
+            // random direction inside the spread cone
+            let mut rotation: Vector3<f32> = muzzle_rotation.clone_owned();
+            if 0.0 < bullet_spread {
+                let spread_angle = rand::random::<f32>() * TWO_PI;
+                let spread_radius = bullet_spread * rand::random::<f32>().sqrt();
+                rotation.x += spread_angle.sin() * spread_radius;
+                rotation.y += spread_angle.cos() * spread_radius;
+            }
+            game_client.get_weapon_manager_mut().fire_bullet(weapon, &self._muzzle_position, &rotation);
+        }
+        // once per shot, not per pellet
+        game_client.get_game_event_manager_mut().publish_event(GameEvent::WeaponFired {
+            _owner_actor_handle: self._owner_actor_handle,
             _position: self._muzzle_position.clone_owned(),
-            _rotation: rotation,
-            ..Default::default()
-        };
-
-        game_client.get_weapon_manager_mut().fire_bullet(self, &render_object_create_info);
+        });
     }
-    fn update_weapon(&mut self, ship_transform_object: &TransformObjectData, _delta_time: f32) {
+    pub fn update_weapon_base(&mut self, ship_transform_object: &TransformObjectData, delta_time: f32) {
+        if 0.0 < self._fire_cooldown {
+            self._fire_cooldown = 0f32.max(self._fire_cooldown - delta_time);
+        }
+
+        self._is_firing = self._fire_requested;
+        self._fire_requested = false;
+
         let weapon_world_matrix = &ship_transform_object._matrix * &self._weapon_slot_transform._matrix;
         self._transform_object.set_position_rotation_scale(&weapon_world_matrix);
         if self._transform_object.update_transform_object() {
@@ -174,4 +272,4 @@ impl WeaponTrait for BeamEmitter {
         }
         self._weapon_render_object.borrow_mut()._transform_object.set_position_rotation_scale(&weapon_world_matrix);
     }
-}
\ No newline at end of file
+}
@@ -15,15 +15,19 @@ use rust_engine_3d::vulkan_context::texture::TextureData;
 use rust_engine_3d::renderer::material::MaterialData;
 use rust_engine_3d::renderer::material_instance::MaterialInstanceData;
 use crate::application::project_scene_manager::SceneDataCreateInfo;
+use crate::game_module::actors::ai_controller::AIProfileData;
+use crate::game_module::faction::{FactionTableData, FactionTableDataCreateInfo};
 use crate::game_module::ship::ship::{ShipDataCreateInfo, ShipData};
 use crate::game_module::ship::ship_controller::ShipControllerData;
 use crate::game_module::weapons::bullet::BulletData;
-use crate::game_module::weapons::weapon::{WeaponDataCreateInfo, WeaponData};
+use crate::game_module::weapons::weapon::{WeaponDataCreateInfo, WeaponData, WeaponSlotData};
 use crate::render_pass::render_pass;
 
 pub const SCENE_FILE_PATH: &str = "scenes";
+pub const AI_PROFILE_DATA_FILE_PATH: &str = "game_datas/ai";
 pub const BUILDING_DATA_FILE_PATH: &str = "game_datas/buildings";
 pub const BULLET_DATA_FILE_PATH: &str = "game_datas/bullets";
+pub const FACTION_TABLE_DATA_FILE_PATH: &str = "game_datas/factions";
 pub const SHIP_CONTROLLER_DATA_FILE_PATH: &str = "game_datas/ship_controllers";
 pub const SHIP_DATA_FILE_PATH: &str = "game_datas/ships";
 pub const WEAPON_DATA_FILE_PATH: &str = "game_datas/weapons";
@@ -34,8 +38,10 @@ pub const EXT_GAME_DATA: &str = "data";
 pub const DEFAULT_GAME_DATA_NAME: &str = "default";
 
 pub type SceneDataCreateInfoMap = ResourceDataMap<SceneDataCreateInfo>;
+pub type AIProfileDataMap = ResourceDataMap<AIProfileData>;
 pub type BuildingDataMap = ResourceDataMap<bool>;
 pub type BulletDataMap = ResourceDataMap<BulletData>;
+pub type FactionTableDataMap = ResourceDataMap<FactionTableData>;
 pub type ShipDataMap = ResourceDataMap<ShipData>;
 pub type ShipControllerDataMap = ResourceDataMap<ShipControllerData>;
 pub type WeaponDataMap = ResourceDataMap<WeaponData>;
@@ -44,8 +50,10 @@ pub type WeaponDataMap = ResourceDataMap<WeaponData>;
 pub struct ProjectResources {
     _engine_resources: *const EngineResources,
     _scene_data_create_infos_map: SceneDataCreateInfoMap,
+    _ai_profile_data_map: AIProfileDataMap,
     _building_data_map: BuildingDataMap,
     _bullet_data_map: BulletDataMap,
+    _faction_table_data_map: FactionTableDataMap,
     _ship_data_map: ShipDataMap,
     _ship_controller_data_map: ShipControllerDataMap,
     _weapon_data_map: WeaponDataMap,
@@ -137,8 +145,10 @@ impl ProjectResources {
         Box::new(ProjectResources {
             _engine_resources: std::ptr::null(),
             _scene_data_create_infos_map: SceneDataCreateInfoMap::new(),
+            _ai_profile_data_map: Default::default(),
             _building_data_map: Default::default(),
             _bullet_data_map: Default::default(),
+            _faction_table_data_map: Default::default(),
             _ship_data_map: Default::default(),
             _ship_controller_data_map: Default::default(),
             _weapon_data_map: Default::default()
@@ -195,9 +205,11 @@ impl ProjectResources {
     // Game Datas
     fn load_game_datas(&mut self) {
         log::info!("    load_game_datas");
+        self.load_faction_table_datas();
         self.load_bullet_datas();
         self.load_weapon_datas();
         self.load_ship_controller_datas();
+        self.load_ai_profile_datas();
         self.load_ship_datas();
     }
 
@@ -205,7 +217,9 @@ impl ProjectResources {
         self.unload_bullet_datas();
         self.unload_weapon_datas();
         self.unload_ship_datas();
+        self.unload_ai_profile_datas();
         self.unload_ship_controller_datas();
+        self.unload_faction_table_datas();
     }
 
     // ship controller data
@@ -248,6 +262,87 @@ impl ProjectResources {
         self._ship_controller_data_map.get(resource_name).unwrap()
     }
 
+    // faction table data
+    fn load_faction_table_datas(&mut self) {
+        let game_data_directory = PathBuf::from(FACTION_TABLE_DATA_FILE_PATH);
+
+        // create faction table data
+        let mut default_faction_table_data_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        default_faction_table_data_file_path.push(&game_data_directory);
+        default_faction_table_data_file_path.push(&DEFAULT_GAME_DATA_NAME);
+        default_faction_table_data_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == default_faction_table_data_file_path.is_file() {
+            let default_faction_table_data_create_info = FactionTableDataCreateInfo::default();
+            let mut write_file = File::create(&default_faction_table_data_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_faction_table_data_create_info).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        // load faction table data
+        let game_data_files: Vec<PathBuf> = self.collect_resources(&game_data_directory, &[EXT_GAME_DATA]);
+        for game_data_file in game_data_files {
+            let game_data_name = get_unique_resource_name(&self._faction_table_data_map, &game_data_directory, &game_data_file);
+            let loaded_contents = system::load(&game_data_file);
+            let faction_table_data_create_info: FactionTableDataCreateInfo = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+            let faction_table_data = FactionTableData::create_faction_table_data(&game_data_name, &faction_table_data_create_info);
+            self._faction_table_data_map.insert(game_data_name.clone(), faction_table_data);
+        }
+    }
+
+    fn unload_faction_table_datas(&mut self) {
+        self._faction_table_data_map.clear();
+    }
+
+    pub fn has_faction_table_data(&self, resource_name: &str) -> bool {
+        self._faction_table_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_faction_table_data(&self, resource_name: &str) -> &RcRefCell<FactionTableData> {
+        self._faction_table_data_map.get(resource_name).unwrap()
+    }
+
+    // ai profile data
+    fn load_ai_profile_datas(&mut self) {
+        let game_data_directory = PathBuf::from(AI_PROFILE_DATA_FILE_PATH);
+
+        // create ai profile data
+        let mut default_ai_profile_data_file_path: PathBuf = PathBuf::from(PROJECT_RESOURCE_PATH);
+        default_ai_profile_data_file_path.push(&game_data_directory);
+        default_ai_profile_data_file_path.push(&DEFAULT_GAME_DATA_NAME);
+        default_ai_profile_data_file_path.set_extension(EXT_GAME_DATA);
+        #[cfg(not(target_os = "android"))]
+        if false == default_ai_profile_data_file_path.is_file() {
+            let default_ai_profile_data = AIProfileData::default();
+            let mut write_file = File::create(&default_ai_profile_data_file_path).expect("Failed to create file");
+            let mut write_contents: String = serde_json::to_string(&default_ai_profile_data).expect("Failed to serialize.");
+            write_contents = write_contents.replace(",\"", ",\n\"");
+            write_file.write(write_contents.as_bytes()).expect("Failed to write");
+        }
+
+        // load ai profile data
+        let game_data_files: Vec<PathBuf> = self.collect_resources(&game_data_directory, &[EXT_GAME_DATA]);
+        for game_data_file in game_data_files {
+            let game_data_name = get_unique_resource_name(&self._ai_profile_data_map, &game_data_directory, &game_data_file);
+            let loaded_contents = system::load(&game_data_file);
+            let ai_profile_data: AIProfileData = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
+            self._ai_profile_data_map.insert(game_data_name.clone(), newRcRefCell(ai_profile_data));
+        }
+    }
+
+    fn unload_ai_profile_datas(&mut self) {
+        self._ai_profile_data_map.clear();
+    }
+
+    pub fn has_ai_profile_data(&self, resource_name: &str) -> bool {
+        self._ai_profile_data_map.get(resource_name).is_some()
+    }
+
+    pub fn get_ai_profile_data(&self, resource_name: &str) -> &RcRefCell<AIProfileData> {
+        self._ai_profile_data_map.get(resource_name).unwrap()
+    }
+
     // ship data
     fn load_ship_datas(&mut self) {
         let game_data_directory = PathBuf::from(SHIP_DATA_FILE_PATH);
@@ -260,7 +355,12 @@ impl ProjectResources {
         #[cfg(not(target_os = "android"))]
         if false == default_ship_data_file_path.is_file() {
             let default_ship_data_create_info = ShipDataCreateInfo {
+                _weapon_solts: vec![WeaponSlotData {
+                    _weapon_data_name: DEFAULT_GAME_DATA_NAME.to_string(),
+                    ..Default::default()
+                }],
                 _controller_data_name: DEFAULT_GAME_DATA_NAME.to_string(),
+                _ai_profile_name: DEFAULT_GAME_DATA_NAME.to_string(),
                 ..Default::default()
             };
             let mut write_file = File::create(&default_ship_data_file_path).expect("Failed to create file");
@@ -276,7 +376,9 @@ impl ProjectResources {
             let loaded_contents = system::load(&game_data_file);
             let ship_data_create_info: ShipDataCreateInfo = serde_json::from_reader(loaded_contents).expect("Failed to deserialize.");
             let ship_controller_data = self.get_ship_controller_data(&ship_data_create_info._controller_data_name);
-            let ship_data = ShipData::create_ship_data(&game_data_name, &ship_data_create_info, ship_controller_data);
+            let ai_profile_name = if ship_data_create_info._ai_profile_name.is_empty() { DEFAULT_GAME_DATA_NAME } else { &ship_data_create_info._ai_profile_name };
+            let ai_profile_data = self.get_ai_profile_data(ai_profile_name);
+            let ship_data = ShipData::create_ship_data(&game_data_name, &ship_data_create_info, ship_controller_data, ai_profile_data);
             self._ship_data_map.insert(game_data_name.clone(), ship_data);
         }
     }